@@ -0,0 +1,475 @@
+//! Settings shared by every launcher folder. They're stored as `key = value` lines in
+//! `config.toml` next to the launcher folders. Lines after a `[preset.<name>]` header belong to
+//! that preset instead, and only take effect when it's picked.
+
+use std::{
+    collections::BTreeMap,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use dirs::data_local_dir;
+
+pub struct Config {
+    pub corner_radius: f32,
+    pub shadows: bool,
+    pub close_on_launch: bool,
+    pub recent_strip: bool,
+    pub recent_count: usize,
+    pub stretch_last_row: bool,
+    pub error_toasts: bool,
+    /// `None` fits the columns to the window width.
+    pub columns: Option<usize>,
+    /// `None` leaves the window at its default size.
+    pub width: Option<f32>,
+    pub height: Option<f32>,
+    pub layout: Layout,
+    pub label_position: LabelPosition,
+    pub when_running: WhenRunning,
+    pub filter_enter: FilterEnter,
+    pub focus_filter: bool,
+    /// Seconds between rescans of the folder, for when the watcher misses changes. `None` relies
+    /// on the watcher alone.
+    pub rescan_interval: Option<u64>,
+    /// The settings each preset overrides, in the order they're written.
+    pub presets: BTreeMap<String, Vec<(String, String)>>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            corner_radius: 2.0,
+            shadows: false,
+            close_on_launch: true,
+            recent_strip: false,
+            recent_count: 5,
+            stretch_last_row: false,
+            error_toasts: false,
+            columns: None,
+            width: None,
+            height: None,
+            layout: Layout::Grid,
+            label_position: LabelPosition::Below,
+            when_running: WhenRunning::Launch,
+            filter_enter: FilterEnter::Single,
+            focus_filter: false,
+            rescan_interval: None,
+            presets: BTreeMap::new(),
+        }
+    }
+}
+
+impl Config {
+    /// Unknown keys and malformed values are ignored, leaving the default in place.
+    pub fn load() -> Self {
+        path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .map(|contents| parse(&contents).0)
+            .unwrap_or_default()
+    }
+
+    pub fn apply_preset(&mut self, name: &str) -> Result<(), String> {
+        let settings = self
+            .presets
+            .get(name)
+            .cloned()
+            .ok_or_else(|| format!("no preset called `{name}`"))?;
+        for (key, value) in settings {
+            self.set(&key, &value)?;
+        }
+        Ok(())
+    }
+
+    /// Writes every setting, defaults included, so the file describes this setup on its own.
+    pub fn export(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    /// Replaces `config.toml` with the settings in `file`. Every line is checked first, so a bad
+    /// file leaves the current settings alone.
+    pub fn import(file: &Path) -> Result<(), String> {
+        let contents = fs::read_to_string(file).map_err(|e| e.to_string())?;
+        let (config, errors) = parse(&contents);
+        if let Some(error) = errors.into_iter().next() {
+            return Err(error);
+        }
+        let config_path = path().ok_or("couldn't find the local app data directory")?;
+        if let Some(dir) = config_path.parent() {
+            fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        config.export(&config_path).map_err(|e| e.to_string())
+    }
+
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        match key {
+            "corner_radius" => self.corner_radius = parse_finite(key, value)?,
+            "shadows" => self.shadows = parse_value(key, value)?,
+            "close_on_launch" => self.close_on_launch = parse_value(key, value)?,
+            "recent_strip" => self.recent_strip = parse_value(key, value)?,
+            "recent_count" => self.recent_count = parse_value(key, value)?,
+            "stretch_last_row" => self.stretch_last_row = parse_value(key, value)?,
+            "error_toasts" => self.error_toasts = parse_value(key, value)?,
+            "columns" if value == "auto" => self.columns = None,
+            "columns" => match parse_value(key, value)? {
+                0 => return Err("`columns` must be at least 1".to_string()),
+                columns => self.columns = Some(columns),
+            },
+            "width" if value == "auto" => self.width = None,
+            "width" => self.width = Some(parse_finite(key, value)?),
+            "height" if value == "auto" => self.height = None,
+            "height" => self.height = Some(parse_finite(key, value)?),
+            "layout" => self.layout = parse_value(key, value)?,
+            "label_position" => self.label_position = parse_value(key, value)?,
+            "when_running" => self.when_running = parse_value(key, value)?,
+            "filter_enter" => self.filter_enter = parse_value(key, value)?,
+            "focus_filter" => self.focus_filter = parse_value(key, value)?,
+            "rescan_interval" if value == "off" => self.rescan_interval = None,
+            "rescan_interval" => match parse_value(key, value)? {
+                0 => return Err("`rescan_interval` must be at least 1".to_string()),
+                seconds => self.rescan_interval = Some(seconds),
+            },
+            _ => return Err(format!("unknown setting `{key}`")),
+        }
+        Ok(())
+    }
+}
+
+/// How the launcher lays out its entries.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Layout {
+    Grid,
+    /// A single swipeable row of large tiles, for touchscreens.
+    Carousel,
+}
+
+impl FromStr for Layout {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, ()> {
+        match s {
+            "grid" => Ok(Layout::Grid),
+            "carousel" => Ok(Layout::Carousel),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for Layout {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Layout::Grid => "grid",
+            Layout::Carousel => "carousel",
+        })
+    }
+}
+
+/// Where each entry's name goes relative to its icon.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum LabelPosition {
+    Below,
+    Right,
+}
+
+impl FromStr for LabelPosition {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, ()> {
+        match s {
+            "below" => Ok(LabelPosition::Below),
+            "right" => Ok(LabelPosition::Right),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for LabelPosition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            LabelPosition::Below => "below",
+            LabelPosition::Right => "right",
+        })
+    }
+}
+
+/// What launching an entry whose program is already running does.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum WhenRunning {
+    /// Start another copy, or let the program decide what to do with a second launch.
+    Launch,
+    /// Switch to the program's window, the way the taskbar does.
+    Focus,
+}
+
+impl FromStr for WhenRunning {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, ()> {
+        match s {
+            "launch" => Ok(WhenRunning::Launch),
+            "focus" => Ok(WhenRunning::Focus),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for WhenRunning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            WhenRunning::Launch => "launch",
+            WhenRunning::Focus => "focus",
+        })
+    }
+}
+
+/// What pressing Enter in the filter box launches.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum FilterEnter {
+    /// The matching entry, once the filter has narrowed it down to one.
+    Single,
+    /// The first matching entry, however many there are.
+    First,
+}
+
+impl FromStr for FilterEnter {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, ()> {
+        match s {
+            "single" => Ok(FilterEnter::Single),
+            "first" => Ok(FilterEnter::First),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for FilterEnter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            FilterEnter::Single => "single",
+            FilterEnter::First => "first",
+        })
+    }
+}
+
+impl fmt::Display for Config {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let settings = [
+            ("corner_radius", self.corner_radius.to_string()),
+            ("shadows", self.shadows.to_string()),
+            ("close_on_launch", self.close_on_launch.to_string()),
+            ("recent_strip", self.recent_strip.to_string()),
+            ("recent_count", self.recent_count.to_string()),
+            ("stretch_last_row", self.stretch_last_row.to_string()),
+            ("error_toasts", self.error_toasts.to_string()),
+            ("columns", or_word(self.columns, "auto")),
+            ("width", or_word(self.width, "auto")),
+            ("height", or_word(self.height, "auto")),
+            ("layout", self.layout.to_string()),
+            ("label_position", self.label_position.to_string()),
+            ("when_running", self.when_running.to_string()),
+            ("filter_enter", self.filter_enter.to_string()),
+            ("focus_filter", self.focus_filter.to_string()),
+            ("rescan_interval", or_word(self.rescan_interval, "off")),
+        ];
+        for (key, value) in settings {
+            writeln!(f, "{key} = {}", toml_value(&value))?;
+        }
+        for (name, settings) in &self.presets {
+            writeln!(f, "\n[preset.{}]", toml_key(name))?;
+            for (key, value) in settings {
+                writeln!(f, "{key} = {}", toml_value(value))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn or_word<T: fmt::Display>(value: Option<T>, word: &str) -> String {
+    value.map_or_else(|| word.to_string(), |value| value.to_string())
+}
+
+/// Numbers and booleans are written bare, anything else as a TOML string.
+fn toml_value(value: &str) -> String {
+    if value.parse::<f64>().is_ok() || value.parse::<bool>().is_ok() {
+        value.to_string()
+    } else {
+        format!("\"{value}\"")
+    }
+}
+
+/// Names that wouldn't be a bare TOML key, such as ones with spaces or dots, are quoted.
+fn toml_key(name: &str) -> String {
+    if name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        name.to_string()
+    } else {
+        format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+}
+
+fn path() -> Option<PathBuf> {
+    data_local_dir().map(|dir| dir.join("kslauncher").join("config.toml"))
+}
+
+/// Returns the settings along with a message for each line that couldn't be used.
+fn parse(contents: &str) -> (Config, Vec<String>) {
+    let mut config = Config::default();
+    let mut errors = vec![];
+    let mut preset = None;
+    for (i, line) in contents.lines().enumerate() {
+        let line_number = i + 1;
+        if is_blank(line) {
+            continue;
+        }
+        if let Some(name) = parse_preset_header(line) {
+            config.presets.entry(name.clone()).or_default();
+            preset = Some(name);
+            continue;
+        }
+        let Some((key, value)) = parse_line(line) else {
+            errors.push(format!("line {line_number}: expected `key = value`"));
+            continue;
+        };
+        // Preset settings are checked against a scratch config, they aren't applied yet.
+        let result = match &preset {
+            Some(name) => Config::default().set(key, value).map(|()| {
+                let settings = config.presets.entry(name.clone()).or_default();
+                settings.push((key.to_string(), value.to_string()));
+            }),
+            None => config.set(key, value),
+        };
+        if let Err(e) = result {
+            errors.push(format!("line {line_number}: {e}"));
+        }
+    }
+    (config, errors)
+}
+
+fn parse_preset_header(line: &str) -> Option<String> {
+    let name = line
+        .trim()
+        .strip_prefix("[preset.")?
+        .strip_suffix(']')?
+        .trim();
+    let quoted = name
+        .strip_prefix('"')
+        .and_then(|name| name.strip_suffix('"'));
+    let name = match quoted {
+        Some(quoted) => unescape(quoted)?,
+        None => name.to_string(),
+    };
+    (!name.is_empty()).then_some(name)
+}
+
+/// Undoes the `\\` and `\"` escapes a quoted preset name is written with.
+fn unescape(quoted: &str) -> Option<String> {
+    let mut name = String::new();
+    let mut chars = quoted.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => name.push(chars.next().filter(|c| matches!(c, '\\' | '"'))?),
+            c => name.push(c),
+        }
+    }
+    Some(name)
+}
+
+fn is_blank(line: &str) -> bool {
+    let line = line.trim();
+    line.is_empty() || line.starts_with('#')
+}
+
+fn parse_line(line: &str) -> Option<(&str, &str)> {
+    if is_blank(line) {
+        return None;
+    }
+    let (key, value) = line.split_once('=')?;
+    let value = value.trim();
+    let value = value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+        .unwrap_or(value);
+    Some((key.trim(), value))
+}
+
+fn parse_value<T: FromStr>(key: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid value `{value}` for `{key}`"))
+}
+
+/// `nan` and `inf` parse as numbers, but no size can use them and TOML can't hold `NaN`.
+fn parse_finite(key: &str, value: &str) -> Result<f32, String> {
+    match parse_value::<f32>(key, value)? {
+        number if number.is_finite() => Ok(number),
+        _ => Err(format!("`{key}` must be a finite number")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(config: &Config) -> Config {
+        let (parsed, errors) = parse(&config.to_string());
+        assert_eq!(errors, Vec::<String>::new());
+        parsed
+    }
+
+    #[test]
+    fn default_config_round_trips() {
+        let config = Config::default();
+        assert_eq!(round_trip(&config).to_string(), config.to_string());
+    }
+
+    #[test]
+    fn changed_settings_and_presets_round_trip() {
+        let mut config = Config {
+            corner_radius: 6.5,
+            columns: Some(4),
+            width: Some(800.0),
+            layout: Layout::Carousel,
+            filter_enter: FilterEnter::First,
+            rescan_interval: Some(30),
+            ..Config::default()
+        };
+        for name in ["compact", "big screen", "v1.2", "say \"hi\" \\ bye"] {
+            let settings = vec![
+                ("columns".to_string(), "auto".to_string()),
+                ("shadows".to_string(), "true".to_string()),
+            ];
+            config.presets.insert(name.to_string(), settings);
+        }
+        let parsed = round_trip(&config);
+        assert_eq!(parsed.to_string(), config.to_string());
+        assert_eq!(parsed.presets, config.presets);
+        assert_eq!(parsed.columns, Some(4));
+        assert_eq!(parsed.width, Some(800.0));
+    }
+
+    #[test]
+    fn preset_names_are_quoted_when_needed() {
+        assert_eq!(toml_key("compact"), "compact");
+        assert_eq!(toml_key("big screen"), "\"big screen\"");
+        assert_eq!(toml_key("v1.2"), "\"v1.2\"");
+        assert_eq!(
+            parse_preset_header("[preset.\"big screen\"]").as_deref(),
+            Some("big screen")
+        );
+        assert_eq!(parse_preset_header("[preset.\"\"]"), None);
+    }
+
+    #[test]
+    fn non_finite_sizes_are_rejected() {
+        for value in ["nan", "NaN", "inf", "-inf"] {
+            let lines = ["corner_radius", "width", "height"].map(|key| format!("{key} = {value}"));
+            let (config, errors) = parse(&lines.join("\n"));
+            assert_eq!(errors.len(), 3, "{value}: {errors:?}");
+            assert_eq!(config.corner_radius, Config::default().corner_radius);
+            assert_eq!(config.width, None);
+        }
+    }
+}
@@ -0,0 +1,229 @@
+//! Draws one element floating over the bottom right corner of another, the way toasts pop up.
+//! iced 0.12 has no widget for stacking elements, so the floating one is drawn as an overlay.
+
+use iced::{mouse, Element, Event, Length, Point, Rectangle, Renderer, Size, Theme, Vector};
+use iced_runtime::core::{
+    event, layout, overlay, renderer,
+    widget::{Operation, Tree},
+    Clipboard, Layout, Overlay, Shell, Widget,
+};
+
+pub struct Floating<'a, Message> {
+    content: Element<'a, Message>,
+    corner: Element<'a, Message>,
+}
+
+impl<'a, Message> Floating<'a, Message> {
+    pub fn new(
+        content: impl Into<Element<'a, Message>>,
+        corner: impl Into<Element<'a, Message>>,
+    ) -> Self {
+        Floating {
+            content: content.into(),
+            corner: corner.into(),
+        }
+    }
+}
+
+impl<'a, Message> Widget<Message, Theme, Renderer> for Floating<'a, Message> {
+    fn size(&self) -> Size<Length> {
+        self.content.as_widget().size()
+    }
+
+    fn layout(
+        &self,
+        tree: &mut Tree,
+        renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        self.content
+            .as_widget()
+            .layout(&mut tree.children[0], renderer, limits)
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        self.content.as_widget().draw(
+            &tree.children[0],
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor,
+            viewport,
+        );
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        vec![Tree::new(&self.content), Tree::new(&self.corner)]
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(&[&self.content, &self.corner]);
+    }
+
+    fn operate(
+        &self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn Operation<Message>,
+    ) {
+        self.content
+            .as_widget()
+            .operate(&mut tree.children[0], layout, renderer, operation);
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) -> event::Status {
+        self.content.as_widget_mut().on_event(
+            &mut tree.children[0],
+            event,
+            layout,
+            cursor,
+            renderer,
+            clipboard,
+            shell,
+            viewport,
+        )
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.content.as_widget().mouse_interaction(
+            &tree.children[0],
+            layout,
+            cursor,
+            viewport,
+            renderer,
+        )
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        translation: Vector,
+    ) -> Option<overlay::Element<'b, Message, Theme, Renderer>> {
+        let (content_tree, corner_tree) = tree.children.split_at_mut(1);
+        let content = self.content.as_widget_mut().overlay(
+            &mut content_tree[0],
+            layout,
+            renderer,
+            translation,
+        );
+        let corner = overlay::Element::new(Box::new(Corner {
+            element: &mut self.corner,
+            tree: &mut corner_tree[0],
+            bounds: layout.bounds() + translation,
+        }));
+        Some(
+            overlay::Group::with_children(content.into_iter().chain(Some(corner)).collect())
+                .overlay(),
+        )
+    }
+}
+
+impl<'a, Message: 'a> From<Floating<'a, Message>> for Element<'a, Message> {
+    fn from(floating: Floating<'a, Message>) -> Self {
+        Element::new(floating)
+    }
+}
+
+struct Corner<'a, 'b, Message> {
+    element: &'b mut Element<'a, Message>,
+    tree: &'b mut Tree,
+    /// Where the content the element floats over was laid out.
+    bounds: Rectangle,
+}
+
+impl<'a, 'b, Message> Overlay<Message, Theme, Renderer> for Corner<'a, 'b, Message> {
+    fn layout(&mut self, renderer: &Renderer, _bounds: Size) -> layout::Node {
+        let limits = layout::Limits::new(Size::ZERO, self.bounds.size());
+        let node = self
+            .element
+            .as_widget()
+            .layout(self.tree, renderer, &limits);
+        let size = node.size();
+        node.move_to(Point::new(
+            self.bounds.x + self.bounds.width - size.width,
+            self.bounds.y + self.bounds.height - size.height,
+        ))
+    }
+
+    fn draw(
+        &self,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+    ) {
+        self.element.as_widget().draw(
+            self.tree,
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor,
+            &layout.bounds(),
+        );
+    }
+
+    fn on_event(
+        &mut self,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+    ) -> event::Status {
+        self.element.as_widget_mut().on_event(
+            self.tree,
+            event,
+            layout,
+            cursor,
+            renderer,
+            clipboard,
+            shell,
+            &layout.bounds(),
+        )
+    }
+
+    fn mouse_interaction(
+        &self,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.element
+            .as_widget()
+            .mouse_interaction(self.tree, layout, cursor, viewport, renderer)
+    }
+}
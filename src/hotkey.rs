@@ -0,0 +1,108 @@
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::mpsc,
+    thread::{self, JoinHandle},
+};
+
+use windows::Win32::{
+    Foundation::{HWND, LPARAM, WPARAM},
+    System::Threading::GetCurrentThreadId,
+    UI::{
+        Input::KeyboardAndMouse::{
+            RegisterHotKey, UnregisterHotKey, HOT_KEY_MODIFIERS, MOD_ALT, MOD_CONTROL,
+            MOD_NOREPEAT, MOD_SHIFT, MOD_WIN, VK_F1, VK_SPACE,
+        },
+        WindowsAndMessaging::{
+            GetMessageW, PeekMessageW, PostThreadMessageW, MSG, PM_NOREMOVE, WM_HOTKEY, WM_QUIT,
+        },
+    },
+};
+
+/// Stops listening when dropped, and only returns once the hotkeys are free to register again.
+pub struct Listener {
+    thread_id: u32,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Drop for Listener {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = PostThreadMessageW(self.thread_id, WM_QUIT, WPARAM(0), LPARAM(0));
+        }
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Parses bindings such as `Ctrl+Alt+K` or `Win+F5`.
+fn parse(binding: &str) -> Option<(HOT_KEY_MODIFIERS, u32)> {
+    let mut modifiers = MOD_NOREPEAT;
+    let mut key = None;
+    for part in binding.split('+').map(str::trim) {
+        match part.to_ascii_lowercase().as_str() {
+            "ctrl" | "control" => modifiers |= MOD_CONTROL,
+            "alt" => modifiers |= MOD_ALT,
+            "shift" => modifiers |= MOD_SHIFT,
+            "win" => modifiers |= MOD_WIN,
+            _ if key.is_none() => key = Some(parse_key(part)?),
+            _ => return None,
+        }
+    }
+    Some((modifiers, key?))
+}
+
+fn parse_key(key: &str) -> Option<u32> {
+    let key = key.to_ascii_uppercase();
+    match key.as_bytes() {
+        [c @ (b'A'..=b'Z' | b'0'..=b'9')] => Some(u32::from(*c)),
+        [b'F', n @ ..] => {
+            let n = std::str::from_utf8(n).ok()?.parse::<u32>().ok()?;
+            (1..=24).contains(&n).then(|| u32::from(VK_F1.0) + n - 1)
+        }
+        b"SPACE" => Some(u32::from(VK_SPACE.0)),
+        _ => None,
+    }
+}
+
+/// Hotkeys belong to the thread that registered them, so each set of bindings gets a thread
+/// running its own message loop. `on_press` is called from that thread.
+pub fn listen(
+    bindings: Vec<(String, PathBuf)>,
+    on_press: impl Fn(PathBuf) + Send + 'static,
+) -> Listener {
+    let (sender, receiver) = mpsc::channel();
+    let thread = thread::spawn(move || unsafe {
+        // Peeking creates the thread's message queue, so the quit message can't arrive too early.
+        let mut msg = MSG::default();
+        let _ = PeekMessageW(&mut msg, HWND(0), 0, 0, PM_NOREMOVE);
+        let _ = sender.send(GetCurrentThreadId());
+        let mut targets = HashMap::new();
+        for (id, (binding, path)) in (1..).zip(bindings) {
+            let Some((modifiers, key)) = parse(&binding) else {
+                continue;
+            };
+            if RegisterHotKey(HWND(0), id, modifiers, key).is_ok() {
+                targets.insert(id, path);
+            }
+        }
+        if targets.is_empty() {
+            return;
+        }
+        while GetMessageW(&mut msg, HWND(0), 0, 0).0 > 0 {
+            if msg.message == WM_HOTKEY {
+                if let Some(path) = targets.get(&(msg.wParam.0 as i32)) {
+                    on_press(path.clone());
+                }
+            }
+        }
+        for id in targets.keys() {
+            let _ = UnregisterHotKey(HWND(0), *id);
+        }
+    });
+    Listener {
+        thread_id: receiver.recv().unwrap(),
+        thread: Some(thread),
+    }
+}
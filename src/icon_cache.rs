@@ -0,0 +1,116 @@
+//! Extracted icons saved as PNGs under `.iconcache`, so later launches can skip the shell. Files
+//! are named after a hash of the entry's path and record the modification time the icon was
+//! extracted at, so an entry that changed just overwrites its old icon.
+
+use std::{
+    collections::hash_map::DefaultHasher,
+    error::Error,
+    fs::{self, File},
+    hash::{Hash, Hasher},
+    io::{self, BufWriter},
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use dirs::data_local_dir;
+use iced::widget::image;
+use iced_runtime::core::image::Data;
+
+const MODIFIED_KEYWORD: &str = "kslauncher-modified";
+
+static HITS: AtomicUsize = AtomicUsize::new(0);
+static MISSES: AtomicUsize = AtomicUsize::new(0);
+
+/// Missing, stale and unreadable files are all treated as misses.
+pub fn load(path: &Path, modified: SystemTime) -> Option<image::Handle> {
+    let icon = read(path, modified);
+    let counter = if icon.is_some() { &HITS } else { &MISSES };
+    counter.fetch_add(1, Ordering::Relaxed);
+    icon
+}
+
+/// How many [`load`]s found their icon in the cache, and how many didn't, since the launcher
+/// started.
+pub fn stats() -> (usize, usize) {
+    (HITS.load(Ordering::Relaxed), MISSES.load(Ordering::Relaxed))
+}
+
+fn read(path: &Path, modified: SystemTime) -> Option<image::Handle> {
+    let mut reader = png::Decoder::new(File::open(cache_file(path)?).ok()?)
+        .read_info()
+        .ok()?;
+    let fresh = reader
+        .info()
+        .uncompressed_latin1_text
+        .iter()
+        .any(|chunk| chunk.keyword == MODIFIED_KEYWORD && chunk.text == stamp(modified));
+    if !fresh {
+        return None;
+    }
+    let mut pixels = vec![0; reader.output_buffer_size()];
+    let frame = reader.next_frame(&mut pixels).ok()?;
+    if frame.color_type != png::ColorType::Rgba || frame.bit_depth != png::BitDepth::Eight {
+        return None;
+    }
+    pixels.truncate(frame.buffer_size());
+    Some(image::Handle::from_pixels(
+        frame.width,
+        frame.height,
+        pixels,
+    ))
+}
+
+/// Only extracted icons are stored, the bundled fallback is left out so it's retried next time.
+pub fn store(
+    path: &Path,
+    modified: SystemTime,
+    icon: &image::Handle,
+) -> Result<(), Box<dyn Error>> {
+    let Data::Rgba {
+        width,
+        height,
+        pixels,
+    } = icon.data()
+    else {
+        return Ok(());
+    };
+    let file = cache_file(path).ok_or("couldn't find the local app data directory")?;
+    if let Some(dir) = file.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut encoder = png::Encoder::new(BufWriter::new(File::create(file)?), *width, *height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.add_text_chunk(MODIFIED_KEYWORD.to_string(), stamp(modified))?;
+    encoder.write_header()?.write_image_data(pixels)?;
+    Ok(())
+}
+
+/// Deletes every cached icon, for every launcher folder.
+pub fn clear() -> io::Result<()> {
+    let dir = dir().ok_or(io::ErrorKind::NotFound)?;
+    match fs::remove_dir_all(dir) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+fn dir() -> Option<PathBuf> {
+    data_local_dir().map(|dir| dir.join("kslauncher").join(".iconcache"))
+}
+
+fn cache_file(path: &Path) -> Option<PathBuf> {
+    let mut hasher = DefaultHasher::new();
+    path.hash(&mut hasher);
+    dir().map(|dir| dir.join(format!("{:016x}.png", hasher.finish())))
+}
+
+fn stamp(modified: SystemTime) -> String {
+    let since_epoch = modified.duration_since(UNIX_EPOCH).unwrap_or_default();
+    format!(
+        "{}.{:09}",
+        since_epoch.as_secs(),
+        since_epoch.subsec_nanos()
+    )
+}
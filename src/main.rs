@@ -1,53 +1,258 @@
 #![windows_subsystem = "windows"]
 
 use std::{
+    collections::{hash_map::RandomState, BTreeMap, BTreeSet, VecDeque},
     convert::Infallible,
     env,
     ffi::OsStr,
     fs,
-    hash::Hasher,
+    hash::{BuildHasher, Hasher},
     io, mem,
-    os::windows::ffi::OsStrExt,
+    os::windows::{
+        fs::{FileTypeExt, MetadataExt},
+        process::CommandExt,
+    },
     path::{Path, PathBuf},
-    process, ptr, sync::Arc,
+    process, ptr,
+    sync::{mpsc, Arc},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 
 use dirs::data_local_dir;
 use iced::{
     alignment::{Horizontal, Vertical},
-    futures::{channel::mpsc::Sender, future, stream, SinkExt, StreamExt},
+    event,
+    futures::{channel::mpsc::Sender, executor, future, stream, SinkExt, StreamExt},
+    keyboard::{self, key::Named, Key, Modifiers},
     subscription,
     theme::{self, Palette, Theme},
-    widget::{image, Button, Container, Image, Space, Text},
-    window::{self, Id}, Application, Color, Command, Element, Length, Settings, Subscription,
+    widget::{
+        button, image, mouse_area, pick_list, scrollable, text_input, tooltip, Button, Container,
+        Image, Space, Text, Tooltip,
+    },
+    window::{self, Id},
+    Application, Border, Color, Command, Element, Length, Settings, Shadow, Size, Subscription,
+    Vector,
 };
-use iced_runtime::futures::subscription::Recipe;
+use iced_runtime::{core::image::Data, futures::subscription::Recipe};
 use notify::event::{ModifyKind, RenameMode};
 use windows::{
-    core::PCWSTR,
+    core::{w, PCWSTR},
     Win32::{
         self,
-        Foundation::{HANDLE, HINSTANCE, HWND},
-        Storage::FileSystem::FILE_ATTRIBUTE_NORMAL,
+        Foundation::{CloseHandle, ERROR_CANCELLED, HANDLE, HINSTANCE, HWND},
+        Storage::FileSystem::{
+            FILE_ATTRIBUTE_HIDDEN, FILE_ATTRIBUTE_NORMAL, FILE_ATTRIBUTE_SYSTEM,
+        },
         System::{
-            Com::{COINIT_APARTMENTTHREADED, COINIT_DISABLE_OLE1DDE},
+            Console::{AttachConsole, ATTACH_PARENT_PROCESS},
             Registry::HKEY,
+            Threading::{WaitForSingleObject, INFINITE},
         },
         UI::{
             Controls::{IImageList, ILD_TRANSPARENT},
             Shell::{
-                SHGetFileInfoW, SHGetImageList, SHELLEXECUTEINFOW, SHFILEINFOW, SHGFI_SYSICONINDEX,
-                SHIL_EXTRALARGE,
+                SHGetFileInfoW, SHGetImageList, SHGetStockIconInfo, SEE_MASK_NOCLOSEPROCESS,
+                SHELLEXECUTEINFOW, SHFILEINFOW, SHGFI_SYSICONINDEX, SHGFI_USEFILEATTRIBUTES,
+                SHGSI_ICON, SHGSI_SMALLICON, SHIL_EXTRALARGE, SHSTOCKICONINFO, SIID_SHIELD,
             },
-            WindowsAndMessaging::{DestroyIcon, HICON},
+            WindowsAndMessaging::{DestroyIcon, HICON, SW_SHOWNOACTIVATE, SW_SHOWNORMAL},
         },
     },
 };
 
-const GRID_WIDTH: usize = 6;
+mod config;
+mod floating;
+mod hotkey;
+mod icon_cache;
+mod manifest;
+mod protocol;
+mod running;
+mod shortcut;
+mod sidecar;
+mod wide;
+
+/// Unless `columns` is set, the grid fits as many columns of roughly this many pixels as the window
+/// has room for.
+const CELL_WIDTH: u32 = 160;
+const TOAST_DURATION: Duration = Duration::from_secs(5);
+const FALLBACK_ICON: &[u8] = include_bytes!("../assets/generic_file.png");
+const CAROUSEL_TILE_WIDTH: f32 = 220.0;
+const CAROUSEL_ID: &str = "carousel";
+const FILTER_ID: &str = "filter";
+/// The colors offered in the entry menu. `accents.json` can hold any other `#rrggbb` color too.
+const ACCENT_COLORS: [&str; 6] = [
+    "#e05252", "#e09a3e", "#d6c840", "#4cb860", "#4c8ee0", "#a366d9",
+];
 
 pub fn main() -> iced::Result {
-    let mut args = env::args().skip(1);
+    let mut args = env::args().skip(1).peekable();
+    if args.peek().is_some_and(|arg| arg == "--register-protocol") {
+        attach_console();
+        if let Err(e) = protocol::register() {
+            eprintln!("Failed to register the kslauncher:// protocol: {e}");
+            process::exit(1);
+        }
+        println!("Registered the kslauncher:// protocol.");
+        return Ok(());
+    }
+    if args.peek().is_some_and(|arg| arg == "--import-startmenu") {
+        attach_console();
+        let Some(name) = args.nth(1) else {
+            eprintln!("Usage: kslauncher --import-startmenu <name>");
+            process::exit(1);
+        };
+        let Some(folder) = data_local_dir().map(|dir| dir.join("kslauncher").join(&name)) else {
+            eprintln!("Couldn't find the local app data directory.");
+            process::exit(1);
+        };
+        match import_start_menu(&name, &folder) {
+            Ok(count) => println!("Imported {count} entries into {}.", folder.display()),
+            Err(e) => {
+                eprintln!("Failed to import Start Menu folder {name}: {e}");
+                process::exit(1);
+            }
+        }
+        return Ok(());
+    }
+    if args.peek().is_some_and(|arg| arg == "--export-settings") {
+        attach_console();
+        let Some(file) = args.nth(1) else {
+            eprintln!("Usage: kslauncher --export-settings <file>");
+            process::exit(1);
+        };
+        if let Err(e) = config::Config::load().export(Path::new(&file)) {
+            eprintln!("Failed to export settings to {file}: {e}");
+            process::exit(1);
+        }
+        println!("Exported settings to {file}.");
+        return Ok(());
+    }
+    if args.peek().is_some_and(|arg| arg == "--import-settings") {
+        attach_console();
+        let Some(file) = args.nth(1) else {
+            eprintln!("Usage: kslauncher --import-settings <file>");
+            process::exit(1);
+        };
+        if let Err(e) = config::Config::import(Path::new(&file)) {
+            eprintln!("Failed to import settings from {file}: {e}");
+            process::exit(1);
+        }
+        println!("Imported settings from {file}.");
+        return Ok(());
+    }
+    if args.peek().is_some_and(|arg| arg == "--rebuild-icons") {
+        attach_console();
+        let Some(launchers) = data_local_dir().map(|dir| dir.join("kslauncher")) else {
+            eprintln!("Couldn't find the local app data directory.");
+            process::exit(1);
+        };
+        // Without a name every launcher folder is rebuilt.
+        let folders = match args.nth(1) {
+            Some(name) => {
+                let folder = launchers.join(&name);
+                if let Err(e) = fs::read_dir(&folder) {
+                    eprintln!("Can't read launcher folder {name}: {e}");
+                    process::exit(1);
+                }
+                vec![folder]
+            }
+            None => fs::read_dir(&launchers)
+                .into_iter()
+                .flatten()
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.is_dir() && path.file_name() != Some(OsStr::new(".iconcache")))
+                .collect(),
+        };
+        if let Err(e) = icon_cache::clear() {
+            eprintln!("Failed to clear the icon cache: {e}");
+            process::exit(1);
+        }
+        let fallback = image::Handle::from_memory(FALLBACK_ICON);
+        let mut count = 0;
+        for folder in folders {
+            for entry in fs::read_dir(&folder).into_iter().flatten().flatten() {
+                let path = entry.path();
+                // Entries that only got the generic icon had nothing to extract.
+                if !sidecar::is_sidecar(&path) && cached_icon(&link_target(&path), true) != fallback
+                {
+                    count += 1;
+                }
+            }
+        }
+        println!("Rebuilt {count} icons.");
+        return Ok(());
+    }
+    if args.peek().is_some_and(|arg| arg == "--hotkeys") {
+        attach_console();
+        let Some(name) = args.nth(1) else {
+            eprintln!("Usage: kslauncher --hotkeys <name>");
+            process::exit(1);
+        };
+        let Some(folder) = data_local_dir().map(|dir| dir.join("kslauncher").join(&name)) else {
+            eprintln!("Couldn't find the local app data directory.");
+            process::exit(1);
+        };
+        if !folder.is_dir() {
+            eprintln!("There is no launcher folder called {name}.");
+            process::exit(1);
+        }
+        run_hotkeys(&folder);
+    }
+    if args.peek().is_some_and(|arg| arg == "--check") {
+        attach_console();
+        let Some(name) = args.nth(1) else {
+            eprintln!("Usage: kslauncher --check <name>");
+            process::exit(1);
+        };
+        let Some(folder) = data_local_dir().map(|dir| dir.join("kslauncher").join(&name)) else {
+            eprintln!("Couldn't find the local app data directory.");
+            process::exit(1);
+        };
+        let problems = check_folder(&folder);
+        for problem in &problems {
+            println!("{problem}");
+        }
+        if !problems.is_empty() {
+            process::exit(1);
+        }
+        println!("No problems found in {}.", folder.display());
+        return Ok(());
+    }
+    if args.peek().is_some_and(|arg| protocol::is_link(arg)) {
+        let Some((name, entry)) = args.next().and_then(|arg| protocol::parse(&arg)) else {
+            attach_console();
+            eprintln!("Not a valid kslauncher:// link.");
+            process::exit(1);
+        };
+        let folder = data_local_dir().map(|dir| dir.join("kslauncher").join(name));
+        if let Some((folder, path)) = folder
+            .as_ref()
+            .zip(entry)
+            .and_then(|(folder, entry)| find_entry(folder, &entry).map(|path| (folder, path)))
+        {
+            if let Err(e) = launch_detached(folder, &path) {
+                attach_console();
+                eprintln!("Failed to launch {}: {e}", path.display());
+                process::exit(1);
+            }
+            return Ok(());
+        }
+        return Launcher::run(Settings {
+            window: window_settings(),
+            flags: LauncherFlags {
+                folder,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+    }
+    let preset = if args.peek().is_some_and(|arg| arg == "--preset") {
+        args.nth(1)
+    } else {
+        None
+    };
     let folder = args
         .next()
         .and_then(|name| data_local_dir().map(|dir| (dir, name)))
@@ -59,23 +264,25 @@ pub fn main() -> iced::Result {
         .zip(new_item.as_ref().and_then(|new_item| new_item.file_name()))
     {
         let new_item = Path::new(&new_item);
-        let r = fs::rename(new_item, folder.join(file_name));
+        let r = fs::rename(new_item, drop_destination(&folder, new_item, file_name));
         match r {
             Ok(()) => Ok(()),
             Err(e) => Launcher::run(Settings {
-                window: window::Settings::default(),
+                window: window_settings(),
                 flags: LauncherFlags {
                     file_move_error: Some(e),
                     folder: Some(folder),
+                    preset,
                 },
                 ..Default::default()
             }),
         }
     } else {
         Launcher::run(Settings {
-            window: window::Settings::default(),
+            window: window_settings(),
             flags: LauncherFlags {
                 folder,
+                preset,
                 ..Default::default()
             },
             ..Default::default()
@@ -83,25 +290,591 @@ pub fn main() -> iced::Result {
     }
 }
 
+/// The launcher handles its own close requests, so a display name still being typed is saved.
+fn window_settings() -> window::Settings {
+    window::Settings {
+        exit_on_close_request: false,
+        ..Default::default()
+    }
+}
+
 struct Launcher {
-    folder_state: Vec<io::Result<(PathBuf, image::Handle)>>,
+    folder_state: Vec<io::Result<Entry>>,
     flags: LauncherFlags,
+    modifiers: Modifiers,
+    paused: bool,
+    hotkeys: Vec<(String, PathBuf)>,
+    config: config::Config,
+    entry_options: sidecar::Sidecar,
+    launched: sidecar::Sidecar,
+    labels: sidecar::Sidecar,
+    accents: sidecar::Sidecar,
+    working_dirs: sidecar::Sidecar,
+    /// Sidecars that exist but couldn't be parsed, and why. Saving over one would lose what it
+    /// holds, so changes to it are refused until it's fixed.
+    unreadable_sidecars: BTreeMap<&'static str, String>,
+    error: Option<String>,
+    toasts: VecDeque<(Instant, String)>,
+    diagnostics: bool,
+    watcher_status: Option<Result<(), String>>,
+    recent_events: VecDeque<String>,
+    maximized: bool,
+    entry_menu: Option<PathBuf>,
+    /// Set once Remove was clicked in the entry menu, which then asks to confirm.
+    confirming_remove: bool,
+    /// The display name being typed into the entry menu.
+    label_edit: String,
+    filter: String,
+    /// Index into the entries currently shown, which the filter may have narrowed.
+    selected: Option<usize>,
+    /// Entries picked with Shift+click or Shift and the arrow keys, which Enter and Remove act on
+    /// together. Kept by path so entries coming and going don't change what's picked.
+    multi_selected: BTreeSet<PathBuf>,
+    /// Where the Shift range started, it spans from here to the selected entry.
+    range_anchor: Option<PathBuf>,
+    /// Set when Enter in the filter starts a launch, so the filter is cleared once it succeeds.
+    clear_filter_on_launch: bool,
+    window_width: u32,
+    preset: Option<String>,
+    finding_broken_shortcuts: bool,
+    broken_shortcuts: Option<Vec<shortcut::Broken>>,
+}
+
+struct Entry {
+    path: PathBuf,
+    icon: Option<image::Handle>,
+    modified: Option<SystemTime>,
+    link: Option<LinkKind>,
+    /// What a shortcut or link points at, set once the icon has loaded.
+    target: Option<PathBuf>,
+}
+
+/// Directory symlinks count as folder links along with junctions, since both point at a folder.
+#[derive(Clone, Copy)]
+enum LinkKind {
+    File,
+    Folder,
+}
+
+impl Entry {
+    /// The icon is loaded separately, see [`load_icons`].
+    fn load(path: PathBuf) -> Self {
+        let link = fs::symlink_metadata(&path).ok().and_then(|metadata| {
+            let file_type = metadata.file_type();
+            if file_type.is_symlink_dir() {
+                Some(LinkKind::Folder)
+            } else if file_type.is_symlink_file() {
+                Some(LinkKind::File)
+            } else {
+                None
+            }
+        });
+        // Links show when their target changed, or the link itself if the target is missing.
+        let modified = fs::metadata(&path)
+            .or_else(|_| fs::symlink_metadata(&path))
+            .and_then(|m| m.modified())
+            .ok();
+        Entry {
+            path,
+            icon: None,
+            modified,
+            link,
+            target: None,
+        }
+    }
+
+    /// Until its icon has loaded an entry shows the generic file icon.
+    fn icon(&self) -> image::Handle {
+        self.icon
+            .clone()
+            .unwrap_or_else(|| image::Handle::from_memory(FALLBACK_ICON))
+    }
 }
 
 #[derive(Default)]
 struct LauncherFlags {
     file_move_error: Option<io::Error>,
     folder: Option<PathBuf>,
+    preset: Option<String>,
 }
 
 #[derive(Debug, Clone)]
 enum Message {
     Open(PathBuf),
+    OpenWithClipboard(PathBuf, String),
+    OpenNewInstance(PathBuf),
     NewEntry(PathBuf),
-    EntryModified,
+    EntryModified(PathBuf),
     RemoveEntry(PathBuf),
     OpenFolder,
     FileDropped(PathBuf),
+    ModifiersChanged(Modifiers),
+    TogglePaused,
+    Rescan,
+    HotkeyPressed(PathBuf),
+    KeyPressed(Key, Modifiers),
+    WatcherStatus(Result<(), String>),
+    Resized(u32),
+    CloseRequested,
+    MaximizedChanged(bool),
+    ShowEntryMenu(PathBuf),
+    CloseEntryMenu,
+    LabelEdited(String),
+    Rename(PathBuf, String),
+    SetAccent(PathBuf, Option<String>),
+    LaunchRandom,
+    RefreshIcon(PathBuf),
+    RebuildIcons,
+    IconLoaded(PathBuf, image::Handle, Option<PathBuf>),
+    FilterChanged(String),
+    SubmitFilter,
+    ExpireToasts(Instant),
+    MoveSelection(Direction),
+    /// Shift+click, which extends the picked range to the entry at this index.
+    SelectRange(usize),
+    ActivateSelection,
+    ClearSelection,
+    RestartElevated,
+    RequestRemove,
+    ConfirmRemove(Vec<PathBuf>),
+    OpenElevated(PathBuf),
+    ApplyPreset(String),
+    FindBrokenShortcuts,
+    BrokenShortcutsFound(Vec<shortcut::Broken>),
+    RepairShortcut(PathBuf, PathBuf),
+    CloseBrokenShortcuts,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Launcher {
+    /// Presets apply on top of `config.toml` rather than the current settings, so switching
+    /// between them doesn't stack their changes.
+    fn apply_preset(&mut self, name: String) -> Command<Message> {
+        let mut config = config::Config::load();
+        if let Err(e) = config.apply_preset(&name) {
+            self.show_error(format!("Failed to apply preset: {e}"));
+            return Command::none();
+        }
+        self.config = config;
+        self.preset = Some(name);
+        self.resize_to_config()
+    }
+
+    fn resize_to_config(&self) -> Command<Message> {
+        if self.config.width.is_none() && self.config.height.is_none() {
+            return Command::none();
+        }
+        let default = window::Settings::default().size;
+        let size = Size::new(
+            self.config.width.unwrap_or(default.width),
+            self.config.height.unwrap_or(default.height),
+        );
+        Command::single(iced_runtime::command::Action::Window(
+            iced_runtime::window::Action::Resize(Id::MAIN, size),
+        ))
+    }
+
+    /// The carousel is a single row, so it has a column for every entry.
+    fn columns(&self) -> usize {
+        if self.config.layout == config::Layout::Carousel {
+            return self.visible_entries().len().max(1);
+        }
+        self.config
+            .columns
+            .unwrap_or_else(|| (self.window_width / CELL_WIDTH).max(1) as usize)
+    }
+
+    fn visible_entries(&self) -> Vec<&io::Result<Entry>> {
+        self.folder_state
+            .iter()
+            .filter(|entry| self.matches_filter(entry))
+            .collect()
+    }
+
+    /// Entries that failed to read have no name to match, so they're only shown unfiltered.
+    /// Entries match on their name or, for shortcuts and links, the path they point at.
+    fn matches_filter(&self, entry: &io::Result<Entry>) -> bool {
+        let filter = self.filter.to_lowercase();
+        match entry {
+            Ok(entry) => {
+                self.display_name(&entry.path)
+                    .to_lowercase()
+                    .contains(&filter)
+                    || entry.target.as_ref().is_some_and(|target| {
+                        target.to_string_lossy().to_lowercase().contains(&filter)
+                    })
+            }
+            Err(_) => self.filter.is_empty(),
+        }
+    }
+
+    /// The entry's label from `labels.json`, or its file name without the extension.
+    fn display_name(&self, path: &Path) -> String {
+        display_name(&self.labels, path)
+    }
+
+    fn load_sidecars(&mut self) {
+        let Some(folder) = &self.flags.folder else {
+            return;
+        };
+        self.unreadable_sidecars.clear();
+        let mut load_checked = |name| {
+            sidecar::load_checked(folder, name).unwrap_or_else(|e| {
+                self.unreadable_sidecars.insert(name, e);
+                sidecar::Sidecar::new()
+            })
+        };
+        let labels = load_checked(sidecar::LABELS);
+        self.accents = load_checked(sidecar::ACCENTS);
+        self.hotkeys = load_hotkeys(folder);
+        self.entry_options = sidecar::load(folder, sidecar::OPTIONS);
+        self.launched = sidecar::load(folder, sidecar::LAUNCHED);
+        self.working_dirs = sidecar::load(folder, sidecar::WORKING_DIRS);
+        if labels != self.labels {
+            self.labels = labels;
+            self.sort_entries();
+        }
+    }
+
+    /// Shows an error instead if `name` couldn't be read when it was loaded.
+    fn can_save_sidecar(&mut self, name: &str) -> bool {
+        match self.unreadable_sidecars.get(name) {
+            Some(e) => {
+                let error = format!("Not saving {name}, fix it first. It couldn't be read: {e}");
+                self.show_error(error);
+                false
+            }
+            None => true,
+        }
+    }
+
+    fn selected_path(&self) -> Option<PathBuf> {
+        self.selected
+            .and_then(|i| self.visible_entries().get(i).copied())
+            .and_then(|entry| entry.as_ref().ok())
+            .map(|entry| entry.path.clone())
+    }
+
+    fn select_path(&mut self, path: Option<PathBuf>) {
+        self.selected = path.and_then(|path| {
+            self.visible_entries()
+                .iter()
+                .position(|entry| entry.as_ref().is_ok_and(|entry| entry.path == path))
+        });
+    }
+
+    /// Puts the entries back in order after their labels changed.
+    fn sort_entries(&mut self) {
+        let selected = self.selected_path();
+        let labels = &self.labels;
+        self.folder_state
+            .sort_by_cached_key(|entry| entry_order(labels, entry));
+        self.select_path(selected);
+    }
+
+    fn clear_selection(&mut self) {
+        self.selected = None;
+        self.multi_selected.clear();
+        self.range_anchor = None;
+    }
+
+    /// Selects every shown entry between the range's anchor and the one at `index`.
+    fn select_range(&mut self, index: usize) {
+        let paths = self
+            .visible_entries()
+            .into_iter()
+            .map(|entry| entry.as_ref().ok().map(|entry| entry.path.clone()))
+            .collect::<Vec<_>>();
+        let Some(index) = paths.len().checked_sub(1).map(|last| index.min(last)) else {
+            return;
+        };
+        if self.range_anchor.is_none() {
+            self.range_anchor = self.selected_path().or_else(|| paths.get(index)?.clone());
+        }
+        let anchor = self
+            .range_anchor
+            .as_ref()
+            .and_then(|anchor| paths.iter().position(|path| path.as_ref() == Some(anchor)))
+            .unwrap_or(index);
+        self.multi_selected = paths[anchor.min(index)..=anchor.max(index)]
+            .iter()
+            .flatten()
+            .cloned()
+            .collect();
+        self.selected = Some(index);
+    }
+
+    /// What an entry menu action applies to: all the picked entries if `path` is one of them,
+    /// otherwise just `path`.
+    fn action_targets(&self, path: &Path) -> Vec<PathBuf> {
+        if self.multi_selected.contains(path) {
+            self.multi_selected.iter().cloned().collect()
+        } else {
+            vec![path.to_path_buf()]
+        }
+    }
+
+    /// Returns `None` if the launch failed, after showing the error. Declining the elevation
+    /// prompt isn't an error, it just doesn't launch anything.
+    fn launch(
+        &mut self,
+        file_name: &Path,
+        parameters: Option<&str>,
+        wait: bool,
+        elevated: bool,
+    ) -> Option<Option<HANDLE>> {
+        let clear_filter = std::mem::take(&mut self.clear_filter_on_launch);
+        match launch_entry(
+            &self.config,
+            &self.entry_options,
+            &self.working_dirs,
+            file_name,
+            parameters,
+            wait,
+            elevated,
+        ) {
+            Ok(process) => {
+                self.error = None;
+                self.record_launch(file_name);
+                if clear_filter {
+                    // In case the launcher stays open, it's ready for the next search.
+                    self.filter.clear();
+                    self.clear_selection();
+                }
+                Some(process)
+            }
+            Err(e) if is_cancelled(&e) => None,
+            Err(e) => {
+                self.show_error(format!(
+                    "Failed to launch {}: {e}",
+                    file_name.file_name().unwrap_or_default().to_string_lossy()
+                ));
+                None
+            }
+        }
+    }
+
+    /// Errors either replace the banner above the grid or, with `error_toasts` set, pop up for a
+    /// few seconds in the corner.
+    fn show_error(&mut self, error: String) {
+        if self.config.error_toasts {
+            self.toasts.push_back((Instant::now(), error));
+        } else {
+            self.error = Some(error);
+        }
+    }
+
+    fn record_launch(&mut self, file_name: &Path) {
+        if let Some(folder) = &self.flags.folder {
+            record_launch(folder, &mut self.launched, file_name);
+        }
+    }
+
+    /// The most recently launched entries that are still in the folder, newest first.
+    fn recent_entries(&self) -> Vec<&Entry> {
+        let mut recent = self
+            .folder_state
+            .iter()
+            .flatten()
+            .filter_map(|entry| {
+                let launched = sidecar::get(&self.launched, &entry.path)?;
+                Some((launched.parse::<u64>().ok()?, entry))
+            })
+            .collect::<Vec<_>>();
+        recent.sort_by(|(a, _), (b, _)| b.cmp(a));
+        recent
+            .into_iter()
+            .take(self.config.recent_count)
+            .map(|(_, entry)| entry)
+            .collect()
+    }
+
+    fn record_event(&mut self, message: &Message, path: &Path) {
+        const RECENT_EVENTS: usize = 8;
+
+        let kind = match message {
+            Message::NewEntry(_) => "added",
+            Message::RemoveEntry(_) => "removed",
+            _ => "modified",
+        };
+        let note = if self.paused { " (ignored)" } else { "" };
+        self.recent_events
+            .push_front(format!("{kind} {}{note}", path.display()));
+        self.recent_events.truncate(RECENT_EVENTS);
+    }
+
+    fn diagnostics_view(&self) -> Element<'_, Message> {
+        let watcher = match &self.watcher_status {
+            _ if self.flags.folder.is_none() => "not watching, no folder".to_string(),
+            None => "starting".to_string(),
+            Some(Ok(())) if self.paused => "active, paused".to_string(),
+            Some(Ok(())) => "active".to_string(),
+            Some(Err(e)) => format!("failed: {e}"),
+        };
+        let failed = self.folder_state.iter().filter(|e| e.is_err()).count();
+        let pending = self
+            .folder_state
+            .iter()
+            .flatten()
+            .filter(|entry| entry.icon.is_none())
+            .count();
+        let (hits, misses) = icon_cache::stats();
+        let hit_rate = match hits + misses {
+            0 => "no lookups yet".to_string(),
+            lookups => format!("{}% of {lookups} lookups", hits * 100 / lookups),
+        };
+        let folder = self.flags.folder.as_deref().unwrap_or(Path::new(""));
+        let lines = [
+            format!("Folder: {}", folder.display()),
+            format!("Watcher: {watcher}"),
+            format!(
+                "Icons loaded: {}, pending: {pending}, failed to read: {failed}",
+                self.folder_state.len() - failed - pending
+            ),
+            format!("Icon cache hits: {hit_rate}"),
+            "Recent events:".to_string(),
+        ];
+        iced::widget::Column::with_children(
+            lines
+                .into_iter()
+                .chain(self.recent_events.iter().map(|event| format!("  {event}")))
+                .map(|line| Text::new(line).size(12.0).into()),
+        )
+        .push(Button::new(Text::new("Rebuild Icon Cache")).on_press(Message::RebuildIcons))
+        .padding(4.0)
+        .into()
+    }
+
+    fn broken_shortcuts_view(&self) -> Option<Element<'_, Message>> {
+        let broken = self.broken_shortcuts.as_deref()?;
+        let summary = if broken.is_empty() {
+            "No broken shortcuts.".to_string()
+        } else {
+            format!("{} broken shortcuts:", broken.len())
+        };
+        let header = iced::widget::row!(
+            Text::new(summary).width(Length::Fill),
+            Button::new(Text::new("Close")).on_press(Message::CloseBrokenShortcuts),
+        )
+        .align_items(iced::Alignment::Center)
+        .into();
+        let rows = broken.iter().map(|broken| {
+            let name = broken
+                .link
+                .file_stem()
+                .unwrap_or_default()
+                .to_string_lossy();
+            let fix: Element<Message> = match &broken.replacement {
+                Some(replacement) => {
+                    Button::new(Text::new(format!("Point at {}", replacement.display())))
+                        .on_press(Message::RepairShortcut(
+                            broken.link.clone(),
+                            replacement.clone(),
+                        ))
+                        .into()
+                }
+                None => Text::new("No replacement found").into(),
+            };
+            iced::widget::row!(
+                Text::new(format!("{name}: {} is missing", broken.target.display()))
+                    .width(Length::Fill),
+                fix,
+            )
+            .align_items(iced::Alignment::Center)
+            .spacing(4.0)
+            .into()
+        });
+        Some(
+            iced::widget::Column::with_children(std::iter::once(header).chain(rows))
+                .spacing(4.0)
+                .padding(4.0)
+                .into(),
+        )
+    }
+
+    /// `index` is the entry's position among the visible entries, for drawing the selection.
+    fn entry_cell(&self, entry: &Entry, index: usize, icon_size: f32) -> Element<'_, Message> {
+        let file_path = &entry.path;
+        let file_name = self.display_name(file_path);
+        let icon = Image::<image::Handle>::new(entry.icon())
+            .content_fit(iced::ContentFit::Contain)
+            .height(Length::Fixed(icon_size));
+        let link = entry.link.map(|link| {
+            Text::new(match link {
+                LinkKind::File => "File link",
+                LinkKind::Folder => "Folder link",
+            })
+            .size(10.0)
+        });
+        let content: Element<Message> = match self.config.label_position {
+            config::LabelPosition::Below => iced::widget::column!(
+                icon.width(Length::Fill),
+                Text::new(file_name)
+                    .vertical_alignment(iced::alignment::Vertical::Center)
+                    .horizontal_alignment(iced::alignment::Horizontal::Center)
+                    .height(Length::FillPortion(1))
+                    .width(Length::Fill)
+            )
+            .push_maybe(link)
+            .align_items(iced::Alignment::Center)
+            .into(),
+            config::LabelPosition::Right => iced::widget::row!(
+                icon.width(Length::Fixed(icon_size)),
+                iced::widget::column!(Text::new(file_name).width(Length::Fill))
+                    .push_maybe(link)
+                    .width(Length::Fill)
+            )
+            .spacing(8.0)
+            .align_items(iced::Alignment::Center)
+            .height(Length::Fill)
+            .into(),
+        };
+        let button = Button::new(content)
+            .on_press(if self.modifiers.shift() {
+                Message::SelectRange(index)
+            } else if self.modifiers.control() {
+                Message::OpenNewInstance(file_path.clone())
+            } else {
+                Message::Open(file_path.clone())
+            })
+            .style(theme::Button::Custom(Box::new(CellStyle {
+                corner_radius: self.config.corner_radius,
+                shadows: self.config.shadows,
+                selected: self.selected == Some(index) || self.multi_selected.contains(file_path),
+                accent: sidecar::get(&self.accents, file_path).and_then(parse_color),
+            })))
+            .width(Length::Fill)
+            .height(Length::Fill);
+        let button = mouse_area(button).on_right_press(Message::ShowEntryMenu(file_path.clone()));
+        match entry.modified {
+            Some(modified) => Tooltip::new(
+                button,
+                Text::new(format!("Modified {}", humanize_time(modified))),
+                tooltip::Position::Bottom,
+            )
+            .style(theme::Container::Box)
+            .into(),
+            None => button.into(),
+        }
+    }
+
+    fn after_launch(&self, file_name: &Path) -> Command<Message> {
+        let keep_open = sidecar::has_option(&self.entry_options, file_name, "keep-open");
+        if self.config.close_on_launch && !keep_open {
+            Command::single(iced_runtime::command::Action::Window(
+                iced_runtime::window::Action::Close(Id::MAIN),
+            ))
+        } else {
+            Command::none()
+        }
+    }
 }
 
 impl Application for Launcher {
@@ -114,14 +887,63 @@ impl Application for Launcher {
     type Flags = LauncherFlags;
 
     fn new(flags: Self::Flags) -> (Self, Command<Message>) {
-        let state = init_state(&flags);
-        (
-            Launcher {
-                folder_state: state,
-                flags,
-            },
-            Command::none(),
-        )
+        let maximized = flags.folder.as_ref().is_some_and(|folder| {
+            sidecar::load(folder, sidecar::WINDOW)
+                .get("maximized")
+                .is_some_and(|maximized| maximized == "true")
+        });
+        let mut launcher = Launcher {
+            folder_state: vec![],
+            flags,
+            modifiers: Modifiers::default(),
+            paused: false,
+            hotkeys: vec![],
+            config: config::Config::load(),
+            entry_options: sidecar::Sidecar::new(),
+            launched: sidecar::Sidecar::new(),
+            labels: sidecar::Sidecar::new(),
+            accents: sidecar::Sidecar::new(),
+            working_dirs: sidecar::Sidecar::new(),
+            unreadable_sidecars: BTreeMap::new(),
+            error: None,
+            toasts: VecDeque::new(),
+            diagnostics: false,
+            watcher_status: None,
+            recent_events: VecDeque::new(),
+            maximized,
+            entry_menu: None,
+            confirming_remove: false,
+            label_edit: String::new(),
+            filter: String::new(),
+            selected: None,
+            multi_selected: BTreeSet::new(),
+            range_anchor: None,
+            clear_filter_on_launch: false,
+            window_width: window::Settings::default().size.width as u32,
+            preset: None,
+            finding_broken_shortcuts: false,
+            broken_shortcuts: None,
+        };
+        launcher.load_sidecars();
+        launcher.folder_state = init_state(&launcher.flags, &launcher.labels);
+        let resize = match launcher.flags.preset.clone() {
+            Some(name) => launcher.apply_preset(name),
+            None => launcher.resize_to_config(),
+        };
+        let maximize = if maximized {
+            Command::single(iced_runtime::command::Action::Window(
+                iced_runtime::window::Action::Maximize(Id::MAIN, true),
+            ))
+        } else {
+            Command::none()
+        };
+        let focus = if launcher.config.focus_filter {
+            text_input::focus(text_input::Id::new(FILTER_ID))
+        } else {
+            Command::none()
+        };
+        let icons = load_icons(&launcher.folder_state, false);
+        (launcher, Command::batch([resize, maximize, focus, icons]))
     }
 
     fn title(&self) -> String {
@@ -138,36 +960,82 @@ impl Application for Launcher {
     }
 
     fn update(&mut self, message: Message) -> Command<Message> {
+        if let Message::NewEntry(path) | Message::RemoveEntry(path) | Message::EntryModified(path) =
+            &message
+        {
+            self.record_event(&message, path);
+        }
         match message {
+            // Installers and the like can be waited on, rescanning once they exit in case they
+            // added entries.
+            Message::Open(file_name)
+                if sidecar::has_option(&self.entry_options, &file_name, "wait") =>
+            {
+                return match self.launch(&file_name, None, true, false) {
+                    Some(Some(process)) => Command::perform(
+                        smol::unblock(move || unsafe {
+                            WaitForSingleObject(process, INFINITE);
+                            let _ = CloseHandle(process);
+                        }),
+                        |()| Message::Rescan,
+                    ),
+                    Some(None) => self.after_launch(&file_name),
+                    None => Command::none(),
+                };
+            }
+            Message::Open(file_name)
+                if sidecar::has_option(&self.entry_options, &file_name, "clipboard") =>
+            {
+                return iced::clipboard::read(move |text| {
+                    Message::OpenWithClipboard(file_name.clone(), text.unwrap_or_default())
+                });
+            }
             Message::Open(file_name) => {
-                // Open file
-                let file_name_wide = OsStr::new(&file_name)
-                    .encode_wide()
-                    .chain(Some(0))
-                    .collect::<Vec<u16>>();
-                unsafe {
-                    let mut shell_info = SHELLEXECUTEINFOW {
-                        cbSize: mem::size_of::<SHELLEXECUTEINFOW>() as u32,
-                        fMask: 0,
-                        hwnd: HWND::default(),
-                        lpVerb: PCWSTR::null(),
-                        lpFile: PCWSTR(file_name_wide.as_ptr()),
-                        lpParameters: PCWSTR::null(),
-                        lpDirectory: PCWSTR::null(),
-                        nShow: 1,
-                        hInstApp: HINSTANCE::default(),
-                        lpIDList: ptr::null_mut(),
-                        lpClass: PCWSTR::null(),
-                        hkeyClass: HKEY::default(),
-                        dwHotKey: 0,
-                        Anonymous: mem::zeroed(),
-                        hProcess: HANDLE::default(),
-                    };
-                    Win32::UI::Shell::ShellExecuteExW(&mut shell_info).unwrap();
+                if self.launch(&file_name, None, false, false).is_some() {
+                    return self.after_launch(&file_name);
+                }
+            }
+            Message::OpenWithClipboard(file_name, text) => {
+                if self
+                    .launch(&file_name, Some(&quote_argument(&text)), false, false)
+                    .is_some()
+                {
+                    return self.after_launch(&file_name);
+                }
+            }
+            // Programs that need administrator can't be started directly, the shell has to ask.
+            Message::OpenNewInstance(file_name) if requires_admin(&file_name) => {
+                return self.update(Message::Open(file_name));
+            }
+            Message::OpenNewInstance(file_name) => {
+                // Start the executable ourselves so the shell can't hand the launch off to a
+                // running instance.
+                let mut command = if shortcut::is_shortcut(&file_name) {
+                    match shortcut::resolve(&file_name) {
+                        Ok(link) if shortcut::is_executable(&link.target) => {
+                            let mut command = process::Command::new(&link.target);
+                            command.raw_arg(&link.arguments);
+                            if let Some(dir) = &link.working_directory {
+                                command.current_dir(dir);
+                            }
+                            command
+                        }
+                        _ => return self.update(Message::Open(file_name)),
+                    }
+                } else if shortcut::is_executable(&file_name) {
+                    process::Command::new(&file_name)
+                } else {
+                    return self.update(Message::Open(file_name));
                 };
-                return Command::single(iced_runtime::command::Action::Window(
-                    iced_runtime::window::Action::Close(Id::MAIN),
-                ));
+                if let Some(dir) = working_directory(&self.working_dirs, &file_name) {
+                    command.current_dir(dir);
+                }
+                if command.spawn().is_err() {
+                    return self.update(Message::Open(file_name));
+                }
+                self.error = None;
+                self.record_launch(&file_name);
+                return self.after_launch(&file_name);
             }
             Message::OpenFolder => {
                 if let Some(folder) = &self.flags.folder {
@@ -177,19 +1045,394 @@ impl Application for Launcher {
                         .unwrap();
                 }
             }
+            // While paused the watcher's events are dropped, resuming rescans the whole folder.
+            Message::NewEntry(_) | Message::RemoveEntry(_) | Message::EntryModified(_)
+                if self.paused => {}
+            Message::NewEntry(file_path) | Message::EntryModified(file_path)
+                if sidecar::is_sidecar(&file_path) =>
+            {
+                self.load_sidecars()
+            }
+            // The watcher is recursive, but only direct children are entries.
+            Message::NewEntry(file_path) if file_path.parent() != self.flags.folder.as_deref() => {}
+            // The selection follows its entry rather than the index other entries shift.
             Message::NewEntry(file_path) => {
-                let icon = get_icon(&file_path);
-                self.folder_state.push(Ok((file_path, icon)));
+                let selected = self.selected_path();
+                let i = insert_sorted(&mut self.folder_state, &self.labels, Entry::load(file_path));
+                self.select_path(selected);
+                return load_icons(&self.folder_state[i..=i], false);
+            }
+            Message::RemoveEntry(file_path) => {
+                self.multi_selected.remove(&file_path);
+                let selected = self.selected_path();
+                self.folder_state.retain(|e| match e {
+                    Ok(entry) => entry.path != file_path,
+                    Err(_) => true,
+                });
+                self.select_path(selected);
+            }
+            Message::EntryModified(file_path) => {
+                if let Some(entry) = self
+                    .folder_state
+                    .iter_mut()
+                    .flatten()
+                    .find(|entry| entry.path == file_path)
+                {
+                    entry.modified = fs::metadata(&file_path).and_then(|m| m.modified()).ok();
+                }
             }
-            Message::RemoveEntry(file_path) => self.folder_state.retain(|e| match e {
-                Ok((path, _handle)) => path != &file_path,
-                Err(_) => true,
-            }),
-            Message::EntryModified => {}
             Message::FileDropped(path) => {
                 if let Some((folder, file_name)) = self.flags.folder.as_ref().zip(path.file_name())
                 {
-                    let _ = fs::rename(&path, folder.join(file_name));
+                    if let Err(e) = fs::rename(&path, drop_destination(folder, &path, file_name)) {
+                        self.show_error(format!(
+                            "Failed to move {} into the launcher folder: {e}",
+                            file_name.to_string_lossy()
+                        ));
+                    }
+                }
+            }
+            Message::ModifiersChanged(modifiers) => self.modifiers = modifiers,
+            Message::TogglePaused => {
+                self.paused = !self.paused;
+                if !self.paused {
+                    return self.update(Message::Rescan);
+                }
+            }
+            // Unchanged entries keep their icons and stay selected, so a periodic rescan that finds
+            // nothing new doesn't disturb anything.
+            Message::Rescan => {
+                // Sidecars edited while paused, or changes the watcher missed, are picked up too.
+                self.load_sidecars();
+                let selected = self.selected_path();
+                let state = init_state(&self.flags, &self.labels);
+                let mut old = mem::replace(&mut self.folder_state, state);
+                for entry in self.folder_state.iter_mut().flatten() {
+                    if let Some(old) = old
+                        .iter_mut()
+                        .flatten()
+                        .find(|old| old.path == entry.path && old.modified == entry.modified)
+                    {
+                        entry.icon = old.icon.take();
+                        entry.target = old.target.take();
+                    }
+                }
+                self.select_path(selected);
+                let paths = self
+                    .folder_state
+                    .iter()
+                    .flatten()
+                    .map(|entry| &entry.path)
+                    .collect::<BTreeSet<_>>();
+                self.multi_selected.retain(|path| paths.contains(path));
+                let stale = self
+                    .folder_state
+                    .iter()
+                    .filter(|entry| entry.as_ref().is_ok_and(|entry| entry.icon.is_none()));
+                return load_icons(stale, false);
+            }
+            // Entries the new filter hides could otherwise be removed without being seen.
+            Message::FilterChanged(filter) => {
+                self.filter = filter;
+                self.clear_selection();
+            }
+            Message::MoveSelection(direction) => {
+                let last = self.visible_entries().len().saturating_sub(1);
+                let next = match (self.selected.map(|i| i.min(last)), direction) {
+                    (None, _) => 0,
+                    (Some(i), Direction::Left) => i.saturating_sub(1),
+                    (Some(i), Direction::Right) => (i + 1).min(last),
+                    (Some(i), Direction::Up) => i.checked_sub(self.columns()).unwrap_or(i),
+                    (Some(i), Direction::Down) => Some(i + self.columns())
+                        .filter(|&below| below <= last)
+                        .unwrap_or(i),
+                };
+                if self.modifiers.shift() {
+                    self.select_range(next);
+                } else {
+                    self.multi_selected.clear();
+                    self.range_anchor = None;
+                    self.selected = Some(next);
+                }
+                // Keep the selected tile in view.
+                if let (config::Layout::Carousel, Some(i)) = (self.config.layout, self.selected) {
+                    return scrollable::snap_to(
+                        scrollable::Id::new(CAROUSEL_ID),
+                        scrollable::RelativeOffset {
+                            x: i as f32 / last.max(1) as f32,
+                            y: 0.0,
+                        },
+                    );
+                }
+            }
+            Message::SelectRange(index) => self.select_range(index),
+            Message::ActivateSelection if !self.multi_selected.is_empty() => {
+                let mut launches = vec![];
+                for path in self.multi_selected.clone() {
+                    launches.push(self.update(Message::Open(path)));
+                }
+                return Command::batch(launches);
+            }
+            Message::ActivateSelection => {
+                let entry = self
+                    .selected
+                    .and_then(|i| self.visible_entries().get(i).copied());
+                if let Some(Ok(entry)) = entry {
+                    return self.update(Message::Open(entry.path.clone()));
+                }
+            }
+            Message::ClearSelection => self.clear_selection(),
+            Message::OpenElevated(file_name) => {
+                self.entry_menu = None;
+                if self.launch(&file_name, None, false, true).is_some() {
+                    return self.after_launch(&file_name);
+                }
+            }
+            Message::ApplyPreset(name) => return self.apply_preset(name),
+            Message::RequestRemove => self.confirming_remove = true,
+            // Folder links are removed as links, which leaves the folder they point at alone. The
+            // watcher drops the entries once the files are gone.
+            Message::ConfirmRemove(file_paths) => {
+                self.entry_menu = None;
+                self.multi_selected.clear();
+                self.range_anchor = None;
+                for file_path in file_paths {
+                    let folder_link = self.folder_state.iter().flatten().any(|entry| {
+                        entry.path == file_path && matches!(entry.link, Some(LinkKind::Folder))
+                    });
+                    let result = if folder_link {
+                        fs::remove_dir(&file_path)
+                    } else {
+                        fs::remove_file(&file_path)
+                    };
+                    match result {
+                        Err(e) if e.kind() != io::ErrorKind::NotFound => self.show_error(format!(
+                            "Failed to remove {}: {e}",
+                            file_path.file_name().unwrap_or_default().to_string_lossy()
+                        )),
+                        _ => {}
+                    }
+                }
+            }
+            Message::RestartElevated => {
+                let folder_name = self
+                    .flags
+                    .folder
+                    .as_deref()
+                    .and_then(Path::file_name)
+                    .and_then(OsStr::to_str);
+                match restart_elevated(folder_name) {
+                    Ok(()) => {
+                        return Command::single(iced_runtime::command::Action::Window(
+                            iced_runtime::window::Action::Close(Id::MAIN),
+                        ));
+                    }
+                    Err(e) if is_cancelled(&e) => {}
+                    Err(e) => self.show_error(format!("Failed to restart as administrator: {e}")),
+                }
+            }
+            Message::ExpireToasts(now) => self
+                .toasts
+                .retain(|(shown, _)| now.duration_since(*shown) < TOAST_DURATION),
+            // Enter opens the filtered entry once the filter has narrowed it down to one, or with
+            // `filter_enter = first` the first one that matches.
+            Message::SubmitFilter => {
+                let entry = self.selected_path().or_else(|| {
+                    let mut matches = self
+                        .folder_state
+                        .iter()
+                        .filter(|entry| self.matches_filter(entry))
+                        .flatten();
+                    let first = matches.next();
+                    match self.config.filter_enter {
+                        config::FilterEnter::First => first,
+                        config::FilterEnter::Single => first.filter(|_| matches.next().is_none()),
+                    }
+                    .map(|entry| entry.path.clone())
+                });
+                if let Some(path) = entry {
+                    self.clear_filter_on_launch = true;
+                    return self.update(Message::Open(path));
+                }
+            }
+            Message::IconLoaded(file_path, icon, target) => {
+                if let Some(entry) = self
+                    .folder_state
+                    .iter_mut()
+                    .flatten()
+                    .find(|entry| entry.path == file_path)
+                {
+                    entry.icon = Some(icon);
+                    entry.target = target;
+                }
+            }
+            // Hotkeys launch without showing or closing the launcher.
+            Message::HotkeyPressed(file_name) => {
+                self.launch(&file_name, None, false, false);
+            }
+            Message::KeyPressed(Key::Character(c), modifiers)
+                if modifiers.control() && modifiers.shift() && c.eq_ignore_ascii_case("d") =>
+            {
+                self.diagnostics = !self.diagnostics;
+            }
+            Message::KeyPressed(Key::Character(c), modifiers)
+                if modifiers.control() && c.eq_ignore_ascii_case("r") =>
+            {
+                return self.update(Message::LaunchRandom);
+            }
+            Message::KeyPressed(..) => {}
+            Message::LaunchRandom => {
+                let entries = self
+                    .visible_entries()
+                    .into_iter()
+                    .flatten()
+                    .collect::<Vec<_>>();
+                if entries.is_empty() {
+                    return Command::none();
+                }
+                // A fresh `RandomState` is randomly keyed, which is all the randomness this needs.
+                let mut hasher = RandomState::new().build_hasher();
+                hasher.write_u128(
+                    SystemTime::now()
+                        .duration_since(UNIX_EPOCH)
+                        .unwrap_or_default()
+                        .as_nanos(),
+                );
+                let entry = entries[hasher.finish() as usize % entries.len()];
+                return self.update(Message::Open(entry.path.clone()));
+            }
+            Message::WatcherStatus(status) => self.watcher_status = Some(status),
+            // There's no event for maximizing, but it always comes with a resize.
+            Message::Resized(width) => {
+                self.window_width = width;
+                return Command::single(iced_runtime::command::Action::Window(
+                    iced_runtime::window::Action::FetchMaximized(
+                        Id::MAIN,
+                        Box::new(Message::MaximizedChanged),
+                    ),
+                ));
+            }
+            Message::MaximizedChanged(maximized) if maximized != self.maximized => {
+                self.maximized = maximized;
+                if let Some(folder) = &self.flags.folder {
+                    let mut window = sidecar::load(folder, sidecar::WINDOW);
+                    window.insert("maximized".to_string(), maximized.to_string());
+                    let _ = sidecar::save(folder, sidecar::WINDOW, &window);
+                }
+            }
+            Message::MaximizedChanged(_) => {}
+            Message::SetAccent(file_path, accent) => {
+                if !self.can_save_sidecar(sidecar::ACCENTS) {
+                    return Command::none();
+                }
+                let Some((folder, name)) = self
+                    .flags
+                    .folder
+                    .as_ref()
+                    .zip(file_path.file_name().and_then(OsStr::to_str))
+                else {
+                    return Command::none();
+                };
+                match accent {
+                    Some(accent) => self.accents.insert(name.to_owned(), accent),
+                    None => self.accents.remove(name),
+                };
+                if let Err(e) = sidecar::save(folder, sidecar::ACCENTS, &self.accents) {
+                    self.show_error(format!("Failed to save the accent color: {e}"));
+                }
+            }
+            Message::ShowEntryMenu(file_path) => {
+                self.confirming_remove = false;
+                self.label_edit = sidecar::get(&self.labels, &file_path)
+                    .unwrap_or_default()
+                    .to_string();
+                self.entry_menu = Some(file_path);
+            }
+            Message::CloseEntryMenu => self.entry_menu = None,
+            Message::CloseRequested => {
+                if let Some(path) = self.entry_menu.clone() {
+                    let label = sidecar::get(&self.labels, &path).unwrap_or_default();
+                    if self.label_edit.trim() != label {
+                        let _ = self.update(Message::Rename(path, self.label_edit.clone()));
+                    }
+                }
+                return Command::single(iced_runtime::command::Action::Window(
+                    iced_runtime::window::Action::Close(Id::MAIN),
+                ));
+            }
+            Message::LabelEdited(label) => self.label_edit = label,
+            // An empty label goes back to showing the file name.
+            Message::Rename(file_path, label) => {
+                self.entry_menu = None;
+                if !self.can_save_sidecar(sidecar::LABELS) {
+                    return Command::none();
+                }
+                let Some((folder, name)) = self
+                    .flags
+                    .folder
+                    .as_ref()
+                    .zip(file_path.file_name().and_then(OsStr::to_str))
+                else {
+                    return Command::none();
+                };
+                let label = label.trim();
+                if label.is_empty() {
+                    self.labels.remove(name);
+                } else {
+                    self.labels.insert(name.to_owned(), label.to_owned());
+                }
+                if let Err(e) = sidecar::save(folder, sidecar::LABELS, &self.labels) {
+                    self.show_error(format!("Failed to save the display name: {e}"));
+                }
+                self.sort_entries();
+            }
+            Message::FindBrokenShortcuts => {
+                self.finding_broken_shortcuts = true;
+                let paths = self
+                    .folder_state
+                    .iter()
+                    .flatten()
+                    .map(|entry| entry.path.clone())
+                    .collect();
+                return Command::perform(
+                    smol::unblock(move || shortcut::find_broken(paths)),
+                    Message::BrokenShortcutsFound,
+                );
+            }
+            Message::BrokenShortcutsFound(broken) => {
+                self.finding_broken_shortcuts = false;
+                self.broken_shortcuts = Some(broken);
+            }
+            Message::RepairShortcut(link, target) => {
+                if let Err(e) = shortcut::set_target(&link, &target) {
+                    self.show_error(format!(
+                        "Failed to repair {}: {e}",
+                        link.file_name().unwrap_or_default().to_string_lossy()
+                    ));
+                    return Command::none();
+                }
+                if let Some(broken) = &mut self.broken_shortcuts {
+                    broken.retain(|broken| broken.link != link);
+                }
+                return self.update(Message::RefreshIcon(link));
+            }
+            Message::CloseBrokenShortcuts => self.broken_shortcuts = None,
+            // Other launcher folders re-extract theirs the next time they open.
+            Message::RebuildIcons => {
+                if let Err(e) = icon_cache::clear() {
+                    self.show_error(format!("Failed to clear the icon cache: {e}"));
+                    return Command::none();
+                }
+                return load_icons(&self.folder_state, true);
+            }
+            Message::RefreshIcon(file_path) => {
+                self.entry_menu = None;
+                let entry = self
+                    .folder_state
+                    .iter()
+                    .position(|entry| entry.as_ref().is_ok_and(|entry| entry.path == file_path));
+                if let Some(i) = entry {
+                    return load_icons(&self.folder_state[i..=i], true);
                 }
             }
         }
@@ -197,59 +1440,69 @@ impl Application for Launcher {
     }
 
     fn view(&self) -> Element<Message> {
-        if self.folder_state.is_empty() {
-            return Text::new("This folder is empty.").into();
-        }
+        let visible = self.visible_entries();
+        let columns = self.columns();
         let content: Element<Message> = match &self.flags.file_move_error {
             Some(e) => Text::new(format!("Failed to add file to launcher folder: {e}")).into(),
+            None if self.folder_state.is_empty() => Text::new("This folder is empty.").into(),
+            None if visible.is_empty() => Text::new("No entries match the filter.").into(),
+            None if self.config.layout == config::Layout::Carousel => scrollable(
+                iced::widget::Row::with_children(visible.iter().enumerate().map(|(i, entry)| {
+                    let cell = match entry {
+                        Ok(entry) => self.entry_cell(entry, i, 128.0),
+                        Err(e) => Text::new(format!("Failed to read file: {e}")).into(),
+                    };
+                    Container::new(cell)
+                        .width(Length::Fixed(CAROUSEL_TILE_WIDTH))
+                        .height(Length::Fill)
+                        .align_x(Horizontal::Center)
+                        .align_y(Vertical::Center)
+                        .padding(6.0)
+                        .into()
+                }))
+                .height(Length::Fill),
+            )
+            .id(scrollable::Id::new(CAROUSEL_ID))
+            .direction(scrollable::Direction::Horizontal(
+                scrollable::Properties::default(),
+            ))
+            .height(Length::Fill)
+            .into(),
             None => iced::widget::Column::with_children(
-                self.folder_state
-                    .chunks(GRID_WIDTH)
-                    .map(|row| {
-                        let empty = (0..(GRID_WIDTH - row.len()))
+                visible
+                    .chunks(columns)
+                    .enumerate()
+                    .map(|(row_index, row)| {
+                        let padding = if self.config.stretch_last_row {
+                            0
+                        } else {
+                            columns - row.len()
+                        };
+                        let empty = (0..padding)
                             .map(|_| Space::new(Length::FillPortion(1), Length::Shrink).into());
                         iced::widget::Row::with_children(
                             row.iter()
-                                .map(|entry| match entry {
-                                    Ok((file_path, image_handle)) => {
-                                        let file_name = file_path
-                                            .file_stem()
-                                            .unwrap_or_default()
-                                            .to_string_lossy()
-                                            .to_string();
-                                        Container::new(
-                                            Button::new(
-                                                iced::widget::column!(
-                                                    Image::<image::Handle>::new(
-                                                        image_handle.clone()
-                                                    )
-                                                    .content_fit(iced::ContentFit::Contain)
-                                                    .height(Length::Fixed(48.0))
-                                                    .width(Length::Fill),
-                                                    Text::new(file_name.clone())
-                                                        .vertical_alignment(
-                                                            iced::alignment::Vertical::Center
-                                                        )
-                                                        .horizontal_alignment(
-                                                            iced::alignment::Horizontal::Center
-                                                        )
-                                                        .height(Length::FillPortion(1))
-                                                        .width(Length::Fill)
-                                                )
-                                                .align_items(iced::Alignment::Center),
-                                            )
-                                            .on_press(Message::Open(file_path.clone()))
-                                            .width(Length::Fill)
-                                            .height(Length::Fill),
-                                        )
-                                        .width(Length::FillPortion(1))
-                                        .height(Length::Fill)
-                                        .align_x(Horizontal::Center)
-                                        .align_y(Vertical::Center)
-                                        .padding(2.0)
-                                        .into()
-                                    }
-                                    Err(e) => Text::new(format!("Failed to read file: {e}")).into(),
+                                .enumerate()
+                                .map(|(column, entry)| match entry {
+                                    Ok(entry) => Container::new(self.entry_cell(
+                                        entry,
+                                        row_index * columns + column,
+                                        48.0,
+                                    ))
+                                    .width(Length::FillPortion(1))
+                                    .height(Length::Fill)
+                                    .align_x(Horizontal::Center)
+                                    .align_y(Vertical::Center)
+                                    .padding(2.0)
+                                    .into(),
+                                    // Sized like any other cell so it doesn't squeeze the rest of
+                                    // its row.
+                                    Err(e) => Container::new(Text::new(format!(
+                                        "Failed to read file: {e}"
+                                    )))
+                                    .width(Length::FillPortion(1))
+                                    .padding(2.0)
+                                    .into(),
                                 })
                                 .chain(empty)
                                 .collect::<Vec<_>>(),
@@ -268,7 +1521,125 @@ impl Application for Launcher {
         )
         .on_press(Message::OpenFolder)
         .width(Length::Fill);
-        iced::widget::column!(open_folder, content).into()
+        let pause = Button::new(
+            Text::new(if self.paused {
+                "Resume Updates"
+            } else {
+                "Pause Updates"
+            })
+            .horizontal_alignment(iced::alignment::Horizontal::Center),
+        )
+        .on_press(Message::TogglePaused);
+        let filter = text_input("Filter", &self.filter)
+            .id(text_input::Id::new(FILTER_ID))
+            .on_input(Message::FilterChanged)
+            .on_submit(Message::SubmitFilter)
+            .width(Length::Fill);
+        let elevate =
+            Button::new(Text::new("Restart as Administrator")).on_press(Message::RestartElevated);
+        let presets = (!self.config.presets.is_empty()).then(|| {
+            pick_list(
+                self.config.presets.keys().cloned().collect::<Vec<_>>(),
+                self.preset.clone(),
+                Message::ApplyPreset,
+            )
+            .placeholder("Preset")
+        });
+        let fix_shortcuts = Button::new(Text::new("Fix Broken Shortcuts")).on_press_maybe(
+            (!self.finding_broken_shortcuts).then_some(Message::FindBrokenShortcuts),
+        );
+        let random = Button::new(Text::new("Surprise Me")).on_press(Message::LaunchRandom);
+        let toolbar =
+            iced::widget::row!(filter, open_folder, random, pause, elevate, fix_shortcuts)
+                .push_maybe(presets);
+        let entry_menu = self.entry_menu.as_ref().map(|path| {
+            let accents = iced::widget::Row::with_children(ACCENT_COLORS.iter().map(|&accent| {
+                Button::new(Space::new(Length::Fixed(16.0), Length::Fixed(16.0)))
+                    .style(theme::Button::Custom(Box::new(SwatchStyle(
+                        parse_color(accent).unwrap(),
+                    ))))
+                    .on_press(Message::SetAccent(path.clone(), Some(accent.to_string())))
+                    .into()
+            }))
+            .push(
+                Button::new(Text::new("No Color")).on_press(Message::SetAccent(path.clone(), None)),
+            )
+            .align_items(iced::Alignment::Center)
+            .spacing(4.0);
+            let actions = iced::widget::row!(
+                text_input(&self.display_name(path), &self.label_edit)
+                    .on_input(Message::LabelEdited)
+                    .on_submit(Message::Rename(path.clone(), self.label_edit.clone()))
+                    .width(Length::Fill),
+                Button::new(Text::new("Run as Administrator"))
+                    .on_press(Message::OpenElevated(path.clone())),
+                Button::new(Text::new("Refresh Icon")).on_press(Message::RefreshIcon(path.clone())),
+                match (self.confirming_remove, self.action_targets(path)) {
+                    (true, targets) => Button::new(Text::new(match targets.len() {
+                        1 => "Confirm Remove".to_string(),
+                        n => format!("Confirm Removing {n} Entries"),
+                    }))
+                    .style(theme::Button::Destructive)
+                    .on_press(Message::ConfirmRemove(targets)),
+                    (false, targets) => Button::new(Text::new(match targets.len() {
+                        1 => "Remove".to_string(),
+                        n => format!("Remove {n} Entries"),
+                    }))
+                    .on_press(Message::RequestRemove),
+                },
+                Button::new(Text::new("Close")).on_press(Message::CloseEntryMenu),
+            )
+            .align_items(iced::Alignment::Center)
+            .spacing(4.0);
+            iced::widget::column!(actions, accents)
+                .spacing(4.0)
+                .padding(4.0)
+        });
+        let error = self.error.as_deref().map(Text::new);
+        let toasts = Container::new(
+            iced::widget::Column::with_children(self.toasts.iter().map(|(_, toast)| {
+                Container::new(Text::new(toast))
+                    .style(theme::Container::Box)
+                    .padding(6.0)
+                    .into()
+            }))
+            .spacing(4.0)
+            .align_items(iced::Alignment::End),
+        )
+        .padding(4.0);
+        let recent = (self.config.recent_strip && self.flags.file_move_error.is_none())
+            .then(|| self.recent_entries())
+            .filter(|recent| !recent.is_empty())
+            .map(|recent| {
+                iced::widget::Row::with_children(recent.into_iter().map(|entry| {
+                    Button::new(
+                        iced::widget::row!(
+                            Image::<image::Handle>::new(entry.icon())
+                                .content_fit(iced::ContentFit::Contain)
+                                .height(Length::Fixed(24.0))
+                                .width(Length::Fixed(24.0)),
+                            Text::new(self.display_name(&entry.path))
+                        )
+                        .spacing(4.0)
+                        .align_items(iced::Alignment::Center),
+                    )
+                    .style(theme::Button::Secondary)
+                    .on_press(Message::Open(entry.path.clone()))
+                    .into()
+                }))
+                .spacing(2.0)
+                .padding(2.0)
+            });
+        let launcher = iced::widget::column!(toolbar)
+            .push_maybe(self.diagnostics.then(|| self.diagnostics_view()))
+            .push_maybe(entry_menu)
+            .push_maybe(self.broken_shortcuts_view())
+            .push_maybe(error)
+            .push_maybe(recent)
+            .push(content);
+        // Always floating, even with no toasts, so the widget tree keeps its shape and the filter
+        // box doesn't lose focus when a toast comes or goes.
+        floating::Floating::new(launcher, toasts).into()
     }
 
     fn theme(&self) -> Theme {
@@ -279,12 +1650,12 @@ impl Application for Launcher {
     }
 
     fn subscription(&self) -> iced::Subscription<Self::Message> {
-        struct RecipeDragNDrop;
-        impl Recipe for RecipeDragNDrop {
-            type Output = PathBuf;
+        struct RecipeWindow;
+        impl Recipe for RecipeWindow {
+            type Output = Message;
 
             fn hash(&self, state: &mut iced_runtime::core::Hasher) {
-                state.write(b"DragNDrop");
+                state.write(b"Window");
             }
 
             fn stream(
@@ -292,51 +1663,728 @@ impl Application for Launcher {
                 input: iced_runtime::futures::subscription::EventStream,
             ) -> iced_runtime::futures::BoxStream<Self::Output> {
                 Box::pin(input.filter_map(|(e, _status)| async move {
-                    if let iced::Event::Window(_id, window::Event::FileDropped(path)) = e {
-                        Some(path)
-                    } else {
-                        None
+                    match e {
+                        iced::Event::Window(_id, window::Event::FileDropped(path)) => {
+                            Some(Message::FileDropped(path))
+                        }
+                        iced::Event::Window(_id, window::Event::Resized { width, .. }) => {
+                            Some(Message::Resized(width))
+                        }
+                        iced::Event::Window(_id, window::Event::CloseRequested) => {
+                            Some(Message::CloseRequested)
+                        }
+                        _ => None,
+                    }
+                }))
+            }
+        }
+        struct RecipeKeyboard;
+        impl Recipe for RecipeKeyboard {
+            type Output = Message;
+
+            fn hash(&self, state: &mut iced_runtime::core::Hasher) {
+                state.write(b"Keyboard");
+            }
+
+            fn stream(
+                self: Box<Self>,
+                input: iced_runtime::futures::subscription::EventStream,
+            ) -> iced_runtime::futures::BoxStream<Self::Output> {
+                Box::pin(input.filter_map(|(e, status)| async move {
+                    match e {
+                        iced::Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) => {
+                            Some(Message::ModifiersChanged(modifiers))
+                        }
+                        // Keys the filter box used, like Enter to submit, don't also navigate.
+                        iced::Event::Keyboard(keyboard::Event::KeyPressed {
+                            key: Key::Named(key),
+                            ..
+                        }) if status == event::Status::Ignored => match key {
+                            Named::ArrowUp => Some(Message::MoveSelection(Direction::Up)),
+                            Named::ArrowDown => Some(Message::MoveSelection(Direction::Down)),
+                            Named::ArrowLeft => Some(Message::MoveSelection(Direction::Left)),
+                            Named::ArrowRight => Some(Message::MoveSelection(Direction::Right)),
+                            Named::Enter => Some(Message::ActivateSelection),
+                            Named::Escape => Some(Message::ClearSelection),
+                            _ => None,
+                        },
+                        iced::Event::Keyboard(keyboard::Event::KeyPressed {
+                            key,
+                            modifiers,
+                            ..
+                        }) => Some(Message::KeyPressed(key, modifiers)),
+                        _ => None,
                     }
                 }))
             }
         }
         let folder = self.flags.folder.clone();
+        let hotkeys = self.hotkeys.clone();
+        let toasts = if self.toasts.is_empty() {
+            Subscription::none()
+        } else {
+            iced::time::every(Duration::from_millis(500)).map(Message::ExpireToasts)
+        };
+        let rescan = match self.config.rescan_interval {
+            Some(seconds) if !self.paused => {
+                iced::time::every(Duration::from_secs(seconds)).map(|_| Message::Rescan)
+            }
+            _ => Subscription::none(),
+        };
         Subscription::batch([
-            Subscription::from_recipe(RecipeDragNDrop).map(Message::FileDropped),
+            toasts,
+            rescan,
+            Subscription::from_recipe(RecipeWindow),
+            Subscription::from_recipe(RecipeKeyboard),
             subscription::channel(0, 16, move |sender| background(sender, folder)),
+            // Keyed on the bindings, so editing hotkeys.json registers the new ones.
+            subscription::channel(hotkeys.clone(), 16, move |sender| {
+                listen_hotkeys(sender, hotkeys)
+            }),
         ])
     }
 }
 
-fn get_icon(file_path: &Path) -> image::Handle {
+/// Picks where a file moved into `folder` should go, following the folder's sort rules and never
+/// replacing an existing file.
+fn drop_destination(folder: &Path, path: &Path, file_name: &OsStr) -> PathBuf {
+    let rules = sidecar::load(folder, sidecar::SORT_RULES);
+    let subfolder = path
+        .extension()
+        .and_then(OsStr::to_str)
+        .and_then(|ext| {
+            rules
+                .iter()
+                .find(|(rule, _)| rule.trim_start_matches('.').eq_ignore_ascii_case(ext))
+        })
+        .map(|(_, subfolder)| folder.join(subfolder))
+        .filter(|subfolder| fs::create_dir_all(subfolder).is_ok());
+    let destination = subfolder.unwrap_or_else(|| folder.to_owned());
+    // Dropping a file where it already is leaves it alone.
+    if path.parent() == Some(&destination) {
+        return path.to_owned();
+    }
+    unique_destination(&destination, file_name)
+}
+
+/// Adds ` (2)`, ` (3)` and so on before the extension until the name isn't taken in `folder`.
+fn unique_destination(folder: &Path, file_name: &OsStr) -> PathBuf {
+    // Broken links don't exist as far as `Path::exists` is concerned, but still take up the name.
+    let is_free = |path: &Path| fs::symlink_metadata(path).is_err();
+    let path = folder.join(file_name);
+    if is_free(&path) {
+        return path;
+    }
+    let file_name = Path::new(file_name);
+    (2..)
+        .map(|n| {
+            let mut name = file_name.file_stem().unwrap_or_default().to_owned();
+            name.push(format!(" ({n})"));
+            if let Some(extension) = file_name.extension() {
+                name.push(".");
+                name.push(extension);
+            }
+            folder.join(name)
+        })
+        .find(|path| is_free(path))
+        .unwrap()
+}
+
+// This is a GUI subsystem executable, so command line output only shows up if we borrow the
+// console of whoever started us.
+fn attach_console() {
     unsafe {
-        Win32::System::Com::CoInitializeEx(None, COINIT_APARTMENTTHREADED | COINIT_DISABLE_OLE1DDE)
-            .unwrap();
-        let mut psfi = SHFILEINFOW::default();
-        let file_path_wide = file_path
-            .as_os_str()
-            .encode_wide()
-            .chain(Some(0))
-            .collect::<Vec<u16>>();
-        let system_image_list = SHGetFileInfoW(
-            PCWSTR(file_path_wide.as_ptr()),
-            FILE_ATTRIBUTE_NORMAL,
-            Some(&mut psfi),
-            mem::size_of::<SHFILEINFOW>() as u32,
-            SHGFI_SYSICONINDEX,
+        let _ = AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+/// Copies the user and all-users Start Menu folders called `name` into `folder`. Shortcuts are
+/// copied as they are, anything else gets a new shortcut pointing at it.
+fn import_start_menu(name: &str, folder: &Path) -> io::Result<usize> {
+    let sources = [
+        dirs::data_dir(),
+        env::var_os("ProgramData").map(PathBuf::from),
+    ]
+    .into_iter()
+    .flatten()
+    .map(|dir| {
+        dir.join("Microsoft")
+            .join("Windows")
+            .join("Start Menu")
+            .join("Programs")
+            .join(name)
+    })
+    .filter(|dir| dir.is_dir())
+    .collect::<Vec<_>>();
+    if sources.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            "no Start Menu folder with that name",
+        ));
+    }
+    fs::create_dir_all(folder)?;
+    let mut count = 0;
+    for dir in &sources {
+        for entry in fs::read_dir(dir)? {
+            let entry = entry?;
+            let source = entry.path();
+            let Some(file_name) = source.file_name() else {
+                continue;
+            };
+            // Skips desktop.ini and anything else Explorer doesn't show in the Start Menu.
+            let hidden = FILE_ATTRIBUTE_HIDDEN.0 | FILE_ATTRIBUTE_SYSTEM.0;
+            if entry.metadata()?.file_attributes() & hidden != 0
+                || file_name.eq_ignore_ascii_case("desktop.ini")
+            {
+                continue;
+            }
+            // Internet shortcuts can be copied as well as regular ones.
+            let copy = shortcut::is_shortcut(&source)
+                || source
+                    .extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("url"));
+            let destination = if copy {
+                folder.join(file_name)
+            } else {
+                // The whole name is kept so `App.exe` and `App.chm` don't both become `App.lnk`.
+                let mut name = file_name.to_owned();
+                name.push(".lnk");
+                folder.join(name)
+            };
+            if destination.exists() {
+                continue;
+            }
+            if copy {
+                fs::copy(&source, &destination)?;
+            } else {
+                shortcut::create(&source, &destination)?;
+            }
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+/// Everything `--check` finds wrong with a launcher folder, one line each.
+fn check_folder(folder: &Path) -> Vec<String> {
+    let read_dir = match fs::read_dir(folder) {
+        Ok(read_dir) => read_dir,
+        Err(e) => return vec![format!("Can't read {}: {e}", folder.display())],
+    };
+    let mut problems = vec![];
+    let mut entries = vec![];
+    for entry in read_dir {
+        match entry {
+            Ok(entry) if sidecar::is_sidecar(&entry.path()) => {}
+            Ok(entry) => entries.push(entry.path()),
+            Err(e) => problems.push(format!("Failed to read an entry: {e}")),
+        }
+    }
+    let name = |path: &Path| {
+        path.file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string()
+    };
+    // Links to something that's gone fail here.
+    for path in &entries {
+        if let Err(e) = fs::metadata(path) {
+            problems.push(format!("{}: {e}", name(path)));
+        }
+    }
+    for broken in shortcut::find_broken(entries.clone()) {
+        let mut problem = format!(
+            "{}: target {} is missing",
+            name(&broken.link),
+            broken.target.display()
         );
-        let extra_large_image_list: IImageList = SHGetImageList(SHIL_EXTRALARGE as i32).unwrap();
-        if system_image_list != 0 {
-            let icon = extra_large_image_list
-                .GetIcon(psfi.iIcon, ILD_TRANSPARENT.0)
-                .unwrap();
-            let image = icon_to_rgba_image(icon);
-            DestroyIcon(icon).unwrap();
-            image
+        if let Some(replacement) = broken.replacement {
+            problem.push_str(&format!(", {} could replace it", replacement.display()));
+        }
+        problems.push(problem);
+    }
+    let names = entries.iter().map(|path| name(path)).collect::<Vec<_>>();
+    for &sidecar_name in sidecar::ENTRY_KEYED {
+        match sidecar::load_checked(folder, sidecar_name) {
+            // Removed entries keep their launch time, which is only bookkeeping, not a setting.
+            Ok(_) if sidecar_name == sidecar::LAUNCHED => {}
+            Ok(sidecar) => problems.extend(
+                sidecar
+                    .keys()
+                    .filter(|key| !names.contains(key))
+                    .map(|key| format!("{sidecar_name}: {key} isn't in the folder")),
+            ),
+            Err(e) => problems.push(format!("{sidecar_name}: {e}")),
+        }
+    }
+    problems
+}
+
+/// Launches an entry the same way from the grid, hotkeys and `kslauncher://` links. With
+/// `when_running = focus` an already running program is switched to instead, which returns
+/// `Ok(None)`.
+fn launch_entry(
+    config: &config::Config,
+    entry_options: &sidecar::Sidecar,
+    working_dirs: &sidecar::Sidecar,
+    file_name: &Path,
+    parameters: Option<&str>,
+    wait: bool,
+    elevated: bool,
+) -> io::Result<Option<HANDLE>> {
+    // Launches that pass something along or wait on the process need a process of their own.
+    if config.when_running == config::WhenRunning::Focus
+        && parameters.is_none()
+        && !wait
+        && !elevated
+        && target_executable(file_name).is_some_and(|exe| running::focus(&exe))
+    {
+        return Ok(None);
+    }
+    let background = sidecar::has_option(entry_options, file_name, "background");
+    let directory = working_directory(working_dirs, file_name);
+    // Asking for elevation up front, rather than letting the shell find out, also covers
+    // targets reached through a symlink.
+    let elevated = elevated || requires_admin(file_name);
+    let target = link_target(file_name);
+    open_file(
+        &target,
+        parameters,
+        directory.as_deref(),
+        wait,
+        background,
+        elevated,
+    )
+}
+
+/// Launches an entry of `folder` without a launcher window, for `kslauncher://` links and
+/// `--hotkeys`. Declining the elevation prompt isn't an error.
+fn launch_detached(folder: &Path, path: &Path) -> io::Result<()> {
+    let options = sidecar::load(folder, sidecar::OPTIONS);
+    let working_dirs = sidecar::load(folder, sidecar::WORKING_DIRS);
+    let config = config::Config::load();
+    match launch_entry(&config, &options, &working_dirs, path, None, false, false) {
+        Ok(_) => {
+            let mut launched = sidecar::load(folder, sidecar::LAUNCHED);
+            record_launch(folder, &mut launched, path);
+            Ok(())
+        }
+        Err(e) if is_cancelled(&e) => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+fn record_launch(folder: &Path, launched: &mut sidecar::Sidecar, file_name: &Path) {
+    let Some(name) = file_name.file_name().and_then(OsStr::to_str) else {
+        return;
+    };
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    launched.insert(name.to_owned(), now.to_string());
+    let _ = sidecar::save(folder, sidecar::LAUNCHED, launched);
+}
+
+/// Finds an entry in `folder` by file name or, failing that, by file stem.
+fn find_entry(folder: &Path, name: &str) -> Option<PathBuf> {
+    let paths = fs::read_dir(folder)
+        .ok()?
+        .flatten()
+        .map(|e| e.path())
+        .filter(|path| !sidecar::is_sidecar(path))
+        .collect::<Vec<_>>();
+    let matches = |part: Option<&OsStr>| {
+        part.and_then(OsStr::to_str)
+            .is_some_and(|part| part.eq_ignore_ascii_case(name))
+    };
+    paths
+        .iter()
+        .find(|path| matches(path.file_name()))
+        .or_else(|| paths.iter().find(|path| matches(path.file_stem())))
+        .cloned()
+}
+
+/// The entry's working directory from `working_dirs.json`, with `%NAME%` environment variables
+/// expanded.
+fn working_directory(working_dirs: &sidecar::Sidecar, path: &Path) -> Option<PathBuf> {
+    sidecar::get(working_dirs, path).map(|dir| PathBuf::from(expand_env_vars(dir)))
+}
+
+/// Variables that aren't set are left as written, like cmd does.
+fn expand_env_vars(s: &str) -> String {
+    let mut out = String::new();
+    let mut rest = s;
+    while let Some(start) = rest.find('%') {
+        let Some(len) = rest[start + 1..].find('%') else {
+            break;
+        };
+        let name = &rest[start + 1..start + 1 + len];
+        out.push_str(&rest[..start]);
+        match env::var(name) {
+            Ok(value) if !name.is_empty() => out.push_str(&value),
+            _ => out.push_str(&rest[start..start + len + 2]),
+        }
+        rest = &rest[start + len + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// With `wait` set, returns a handle to the launched process if the shell started one. The
+/// caller is responsible for closing it.
+///
+/// A `background` launch shows the program's window without activating it, so focus stays put.
+///
+/// Without a `directory` the program starts wherever the shell picks, which for a shortcut is its
+/// Start in folder.
+fn open_file(
+    file_name: &Path,
+    parameters: Option<&str>,
+    directory: Option<&Path>,
+    wait: bool,
+    background: bool,
+    elevated: bool,
+) -> io::Result<Option<HANDLE>> {
+    let file_name_wide = wide::path_to_wide(file_name)?;
+    let parameters_wide = parameters.map(|p| p.encode_utf16().chain(Some(0)).collect::<Vec<_>>());
+    let directory_wide = directory.map(wide::path_to_wide).transpose()?;
+    unsafe {
+        let mut shell_info = SHELLEXECUTEINFOW {
+            cbSize: mem::size_of::<SHELLEXECUTEINFOW>() as u32,
+            fMask: if wait { SEE_MASK_NOCLOSEPROCESS } else { 0 },
+            hwnd: HWND::default(),
+            lpVerb: if elevated {
+                w!("runas")
+            } else {
+                PCWSTR::null()
+            },
+            lpFile: PCWSTR(file_name_wide.as_ptr()),
+            lpParameters: parameters_wide
+                .as_ref()
+                .map_or(PCWSTR::null(), |p| PCWSTR(p.as_ptr())),
+            lpDirectory: directory_wide
+                .as_ref()
+                .map_or(PCWSTR::null(), |d| PCWSTR(d.as_ptr())),
+            nShow: if background {
+                SW_SHOWNOACTIVATE
+            } else {
+                SW_SHOWNORMAL
+            }
+            .0,
+            hInstApp: HINSTANCE::default(),
+            lpIDList: ptr::null_mut(),
+            lpClass: PCWSTR::null(),
+            hkeyClass: HKEY::default(),
+            dwHotKey: 0,
+            Anonymous: mem::zeroed(),
+            hProcess: HANDLE::default(),
+        };
+        Win32::UI::Shell::ShellExecuteExW(&mut shell_info)?;
+        Ok((!shell_info.hProcess.is_invalid()).then_some(shell_info.hProcess))
+    }
+}
+
+/// Whether the user declined the elevation prompt for a `runas` launch.
+fn is_cancelled(e: &io::Error) -> bool {
+    e.raw_os_error() == Some(ERROR_CANCELLED.to_hresult().0)
+}
+
+/// Starts another kslauncher for the same folder with the `runas` verb, which asks for elevation.
+/// Everything the new instance launches inherits it.
+fn restart_elevated(folder_name: Option<&str>) -> io::Result<()> {
+    let exe_wide = wide::path_to_wide(&env::current_exe()?)?;
+    let parameters_wide = folder_name.map(|name| {
+        quote_argument(name)
+            .encode_utf16()
+            .chain(Some(0))
+            .collect::<Vec<_>>()
+    });
+    unsafe {
+        let mut shell_info = SHELLEXECUTEINFOW {
+            cbSize: mem::size_of::<SHELLEXECUTEINFOW>() as u32,
+            lpVerb: w!("runas"),
+            lpFile: PCWSTR(exe_wide.as_ptr()),
+            lpParameters: parameters_wide
+                .as_ref()
+                .map_or(PCWSTR::null(), |p| PCWSTR(p.as_ptr())),
+            nShow: SW_SHOWNORMAL.0,
+            ..Default::default()
+        };
+        Win32::UI::Shell::ShellExecuteExW(&mut shell_info)?;
+    }
+    Ok(())
+}
+
+/// Quotes `arg` so that a program splitting its command line the usual way sees it as a single
+/// argument.
+fn quote_argument(arg: &str) -> String {
+    let mut quoted = String::from("\"");
+    let mut backslashes = 0;
+    for c in arg.chars().filter(|&c| c != '\0') {
+        if c == '\\' {
+            backslashes += 1;
+            continue;
+        }
+        // Backslashes are only special when they come right before a quote.
+        let escaped = if c == '"' {
+            backslashes * 2 + 1
+        } else {
+            backslashes
+        };
+        quoted.push_str(&"\\".repeat(escaped));
+        quoted.push(c);
+        backslashes = 0;
+    }
+    quoted.push_str(&"\\".repeat(backslashes * 2));
+    quoted.push('"');
+    quoted
+}
+
+struct CellStyle {
+    corner_radius: f32,
+    shadows: bool,
+    selected: bool,
+    accent: Option<Color>,
+}
+
+impl CellStyle {
+    /// The selection border takes the place of an accent border while the entry is selected.
+    fn decorate(&self, appearance: button::Appearance) -> button::Appearance {
+        let border_color = if self.selected {
+            Some(appearance.text_color)
         } else {
-            unimplemented!()
+            self.accent
+        };
+        button::Appearance {
+            border: match border_color {
+                Some(color) => Border {
+                    color,
+                    width: 2.0,
+                    radius: self.corner_radius.into(),
+                },
+                None => Border {
+                    radius: self.corner_radius.into(),
+                    ..appearance.border
+                },
+            },
+            shadow: if self.shadows {
+                Shadow {
+                    color: Color::from_rgba8(0, 0, 0, 0.5),
+                    offset: Vector::new(0.0, 2.0),
+                    blur_radius: 6.0,
+                }
+            } else {
+                appearance.shadow
+            },
+            ..appearance
+        }
+    }
+}
+
+impl button::StyleSheet for CellStyle {
+    type Style = Theme;
+
+    fn active(&self, style: &Theme) -> button::Appearance {
+        self.decorate(style.active(&theme::Button::Primary))
+    }
+
+    fn hovered(&self, style: &Theme) -> button::Appearance {
+        self.decorate(style.hovered(&theme::Button::Primary))
+    }
+
+    fn pressed(&self, style: &Theme) -> button::Appearance {
+        self.decorate(style.pressed(&theme::Button::Primary))
+    }
+}
+
+struct SwatchStyle(Color);
+
+impl button::StyleSheet for SwatchStyle {
+    type Style = Theme;
+
+    fn active(&self, _style: &Theme) -> button::Appearance {
+        button::Appearance {
+            background: Some(self.0.into()),
+            border: Border {
+                radius: 8.0.into(),
+                ..Border::default()
+            },
+            ..button::Appearance::default()
+        }
+    }
+}
+
+/// Parses `#rrggbb` colors, like the ones in `accents.json`.
+fn parse_color(hex: &str) -> Option<Color> {
+    let hex = hex.strip_prefix('#').filter(|hex| hex.len() == 6)?;
+    let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+    Some(Color::from_rgb8(channel(0)?, channel(2)?, channel(4)?))
+}
+
+/// Symlinks and junctions are launched from, and take their icon from, whatever they point at.
+/// Anything else is its own target.
+fn link_target(path: &Path) -> PathBuf {
+    let Ok(target) = fs::read_link(path) else {
+        return path.to_owned();
+    };
+    // Junctions point at verbatim `\\?\C:\...` paths, which not every shell function accepts.
+    let target = match target.to_str().and_then(|t| t.strip_prefix(r"\\?\")) {
+        Some(t) if t.get(1..2) == Some(":") => PathBuf::from(t),
+        _ => target,
+    };
+    path.parent().unwrap_or(Path::new("")).join(target)
+}
+
+/// Loads the icons of `entries` on a blocking thread, producing [`Message::IconLoaded`] for each
+/// one as it's ready. With `refresh` set the cache is skipped and its icons are replaced.
+fn load_icons<'a>(
+    entries: impl IntoIterator<Item = &'a io::Result<Entry>>,
+    refresh: bool,
+) -> Command<Message> {
+    let paths = entries
+        .into_iter()
+        .flatten()
+        .map(|entry| entry.path.clone())
+        .collect::<Vec<_>>();
+    Command::run(
+        smol::Unblock::new(paths.into_iter().map(move |path| {
+            // Resolving a shortcut is slow, so it's done once for both the shield and the target.
+            let resolved = resolve_target(&path);
+            let icon = cached_icon(&link_target(&path), refresh);
+            let icon = if resolved.as_deref().is_some_and(target_requires_admin) {
+                with_shield(icon)
+            } else {
+                icon
+            };
+            let target =
+                resolved.filter(|target| !target.as_os_str().is_empty() && *target != path);
+            (path, icon, target)
+        })),
+        |(path, icon, target)| Message::IconLoaded(path, icon, target),
+    )
+}
+
+/// The executable launching `path` starts, following shortcuts and links, if it starts one
+/// directly.
+fn target_executable(path: &Path) -> Option<PathBuf> {
+    resolve_target(path).filter(|target| shortcut::is_executable(target))
+}
+
+/// Follows a shortcut or link to what it points at. Anything else is its own target.
+fn resolve_target(path: &Path) -> Option<PathBuf> {
+    if shortcut::is_shortcut(path) {
+        Some(shortcut::resolve(path).ok()?.target)
+    } else {
+        Some(link_target(path))
+    }
+}
+
+fn requires_admin(path: &Path) -> bool {
+    resolve_target(path).is_some_and(|target| target_requires_admin(&target))
+}
+
+/// Whether `target`, already resolved from an entry, is a program that runs as administrator.
+fn target_requires_admin(target: &Path) -> bool {
+    shortcut::is_executable(target) && manifest::requires_admin(target)
+}
+
+/// Draws the UAC shield over the bottom right corner of `icon`, the way Explorer marks programs
+/// that run as administrator.
+fn with_shield(icon: image::Handle) -> image::Handle {
+    let shield = unsafe {
+        let mut info = SHSTOCKICONINFO {
+            cbSize: mem::size_of::<SHSTOCKICONINFO>() as u32,
+            ..Default::default()
+        };
+        if SHGetStockIconInfo(SIID_SHIELD, SHGSI_ICON | SHGSI_SMALLICON, &mut info).is_err() {
+            return icon;
+        }
+        let shield = icon_to_rgba_image(info.hIcon);
+        let _ = DestroyIcon(info.hIcon);
+        shield
+    };
+    let (
+        Data::Rgba {
+            width,
+            height,
+            pixels,
+        },
+        Data::Rgba {
+            width: shield_width,
+            height: shield_height,
+            pixels: shield_pixels,
+        },
+    ) = (icon.data(), shield.data())
+    else {
+        return icon;
+    };
+    let mut out = pixels.to_vec();
+    let left = width.saturating_sub(*shield_width);
+    let top = height.saturating_sub(*shield_height);
+    for y in 0..*shield_height.min(height) {
+        for x in 0..*shield_width.min(width) {
+            let from = ((y * shield_width + x) * 4) as usize;
+            let to = (((top + y) * width + left + x) * 4) as usize;
+            let alpha = u32::from(shield_pixels[from + 3]);
+            for c in 0..3 {
+                out[to + c] = ((u32::from(shield_pixels[from + c]) * alpha
+                    + u32::from(out[to + c]) * (255 - alpha))
+                    / 255) as u8;
+            }
+            out[to + 3] = out[to + 3].max(shield_pixels[from + 3]);
         }
     }
+    image::Handle::from_pixels(*width, *height, out)
+}
+
+fn cached_icon(file_path: &Path, refresh: bool) -> image::Handle {
+    let Ok(modified) = fs::metadata(file_path).and_then(|m| m.modified()) else {
+        return get_icon(file_path);
+    };
+    if !refresh {
+        if let Some(icon) = icon_cache::load(file_path, modified) {
+            return icon;
+        }
+    }
+    let icon = get_icon(file_path);
+    let _ = icon_cache::store(file_path, modified, &icon);
+    icon
+}
+
+fn get_icon(file_path: &Path) -> image::Handle {
+    shortcut::init_com().unwrap();
+    unsafe {
+        let mut psfi = SHFILEINFOW::default();
+        // Paths the shell can't be given still get the generic file icon.
+        let system_image_list = match wide::path_to_wide(file_path) {
+            Ok(file_path_wide) => SHGetFileInfoW(
+                PCWSTR(file_path_wide.as_ptr()),
+                FILE_ATTRIBUTE_NORMAL,
+                Some(&mut psfi),
+                mem::size_of::<SHFILEINFOW>() as u32,
+                SHGFI_SYSICONINDEX,
+            ),
+            Err(_) => SHGetFileInfoW(
+                w!("file"),
+                FILE_ATTRIBUTE_NORMAL,
+                Some(&mut psfi),
+                mem::size_of::<SHFILEINFOW>() as u32,
+                SHGFI_SYSICONINDEX | SHGFI_USEFILEATTRIBUTES,
+            ),
+        };
+        // Some paths, like ones on network shares, have no entry in the system image list.
+        if system_image_list == 0 {
+            return image::Handle::from_memory(FALLBACK_ICON);
+        }
+        let extra_large_image_list: IImageList = SHGetImageList(SHIL_EXTRALARGE as i32).unwrap();
+        let icon = extra_large_image_list
+            .GetIcon(psfi.iIcon, ILD_TRANSPARENT.0)
+            .unwrap();
+        let image = icon_to_rgba_image(icon);
+        DestroyIcon(icon).unwrap();
+        image
+    }
 }
 
 unsafe fn icon_to_rgba_image(icon: HICON) -> image::Handle {
@@ -413,20 +2461,19 @@ unsafe fn icon_to_rgba_image(icon: HICON) -> image::Handle {
     image::Handle::from_pixels(width, height, buf)
 }
 
-fn init_state(flags: &LauncherFlags) -> Vec<Result<(PathBuf, image::Handle), io::Error>> {
+fn init_state(flags: &LauncherFlags, labels: &sidecar::Sidecar) -> Vec<Result<Entry, io::Error>> {
     match &flags.folder {
         Some(folder) => {
             let _ = fs::create_dir_all(folder);
             match fs::read_dir(folder) {
-                Ok(read_dir) => read_dir
-                    .map(|r| {
-                        r.map(|e| {
-                            let path = e.path();
-                            let icon = get_icon(&path);
-                            (path, icon)
-                        })
-                    })
-                    .collect::<Vec<_>>(),
+                Ok(read_dir) => {
+                    let mut state = read_dir
+                        .filter(|r| !r.as_ref().is_ok_and(|e| sidecar::is_sidecar(&e.path())))
+                        .map(|r| r.map(|e| Entry::load(e.path())))
+                        .collect::<Vec<_>>();
+                    state.sort_by_cached_key(|entry| entry_order(labels, entry));
+                    state
+                }
                 Err(e) => {
                     vec![Err(e)]
                 }
@@ -438,6 +2485,132 @@ fn init_state(flags: &LauncherFlags) -> Vec<Result<(PathBuf, image::Handle), io:
     }
 }
 
+/// The entry's label from `labels.json`, or its file name without the extension.
+fn display_name(labels: &sidecar::Sidecar, path: &Path) -> String {
+    match sidecar::get(labels, path) {
+        Some(label) => label.to_string(),
+        None => file_stem(path),
+    }
+}
+
+fn file_stem(path: &Path) -> String {
+    path.file_stem()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_string()
+}
+
+/// Entries sort case-insensitively by the name they're shown with, then by file name without the
+/// extension, with ones that failed to read at the end.
+fn entry_order(labels: &sidecar::Sidecar, entry: &io::Result<Entry>) -> (bool, String, String) {
+    match entry {
+        Ok(entry) => (
+            false,
+            display_name(labels, &entry.path).to_lowercase(),
+            file_stem(&entry.path).to_lowercase(),
+        ),
+        Err(_) => (true, String::new(), String::new()),
+    }
+}
+
+/// Returns the index the entry was inserted at.
+fn insert_sorted(
+    state: &mut Vec<io::Result<Entry>>,
+    labels: &sidecar::Sidecar,
+    entry: Entry,
+) -> usize {
+    let entry = Ok(entry);
+    let key = entry_order(labels, &entry);
+    let i = state.partition_point(|other| entry_order(labels, other) <= key);
+    state.insert(i, entry);
+    i
+}
+
+fn humanize_time(time: SystemTime) -> String {
+    const MINUTE: u64 = 60;
+    const HOUR: u64 = 60 * MINUTE;
+    const DAY: u64 = 24 * HOUR;
+
+    let plural = |n: u64, unit: &str| {
+        if n == 1 {
+            format!("1 {unit} ago")
+        } else {
+            format!("{n} {unit}s ago")
+        }
+    };
+    let secs = SystemTime::now()
+        .duration_since(time)
+        .unwrap_or_default()
+        .as_secs();
+    if secs < MINUTE {
+        "just now".to_string()
+    } else if secs < HOUR {
+        plural(secs / MINUTE, "minute")
+    } else if secs < DAY {
+        plural(secs / HOUR, "hour")
+    } else if secs < 2 * DAY {
+        "yesterday".to_string()
+    } else if secs < 7 * DAY {
+        plural(secs / DAY, "day")
+    } else if secs < 30 * DAY {
+        plural(secs / (7 * DAY), "week")
+    } else if secs < 365 * DAY {
+        plural(secs / (30 * DAY), "month")
+    } else {
+        plural(secs / (365 * DAY), "year")
+    }
+}
+
+async fn listen_hotkeys(sender: Sender<Message>, bindings: Vec<(String, PathBuf)>) -> Infallible {
+    let _listener = hotkey::listen(bindings, move |path| {
+        let _ = executor::block_on(sender.clone().send(Message::HotkeyPressed(path)));
+    });
+    future::pending().await
+}
+
+/// The bindings in `folder`'s hotkeys.json, each with the entry it launches.
+fn load_hotkeys(folder: &Path) -> Vec<(String, PathBuf)> {
+    sidecar::load(folder, sidecar::HOTKEYS)
+        .into_iter()
+        .map(|(file_name, binding)| (binding, folder.join(file_name)))
+        .collect()
+}
+
+/// Keeps `folder`'s hotkeys registered without a window, for `--hotkeys`. The bindings are
+/// registered again whenever hotkeys.json changes.
+fn run_hotkeys(folder: &Path) -> ! {
+    use notify::{RecursiveMode, Watcher};
+
+    // `None` means hotkeys.json changed.
+    let (sender, receiver) = mpsc::channel();
+    let changed = sender.clone();
+    let watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        let is_hotkeys = |path: &PathBuf| path.file_name() == Some(OsStr::new(sidecar::HOTKEYS));
+        if event.is_ok_and(|event| event.paths.iter().any(is_hotkeys)) {
+            let _ = changed.send(None);
+        }
+    })
+    .and_then(|mut watcher| {
+        watcher
+            .watch(folder, RecursiveMode::NonRecursive)
+            .map(|()| watcher)
+    });
+    if let Err(e) = &watcher {
+        eprintln!("Not watching hotkeys.json for changes: {e}");
+    }
+    loop {
+        let pressed = sender.clone();
+        let _listener = hotkey::listen(load_hotkeys(folder), move |path| {
+            let _ = pressed.send(Some(path));
+        });
+        while let Ok(Some(path)) = receiver.recv() {
+            if let Err(e) = launch_detached(folder, &path) {
+                eprintln!("Failed to launch {}: {e}", path.display());
+            }
+        }
+    }
+}
+
 async fn background(sender: Sender<Message>, folder_to_monitor: Option<PathBuf>) -> Infallible {
     use notify::{event::EventKind, RecursiveMode, Watcher};
 
@@ -472,7 +2645,10 @@ async fn background(sender: Sender<Message>, folder_to_monitor: Option<PathBuf>)
                     EventKind::Modify(_) => {
                         let mut sender = self.sender.clone();
                         smol::spawn(async move {
-                            sender.send(Message::EntryModified).await.unwrap();
+                            let mut s = stream::iter(
+                                event.paths.into_iter().map(Message::EntryModified).map(Ok),
+                            );
+                            sender.send_all(&mut s).await.unwrap();
                         })
                         .detach();
                     }
@@ -485,9 +2661,111 @@ async fn background(sender: Sender<Message>, folder_to_monitor: Option<PathBuf>)
         let event_handler = FolderEventHandler {
             sender: sender.clone(),
         };
-        let mut watcher = notify::recommended_watcher(event_handler).unwrap();
-        watcher.watch(&folder, RecursiveMode::Recursive).unwrap();
+        let watcher = notify::recommended_watcher(event_handler).and_then(|mut watcher| {
+            watcher
+                .watch(&folder, RecursiveMode::Recursive)
+                .map(|()| watcher)
+        });
+        let status = watcher.as_ref().map(|_| ()).map_err(|e| e.to_string());
+        let _ = sender.clone().send(Message::WatcherStatus(status)).await;
         future::pending().await
     }
     future::pending().await
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str) -> Entry {
+        Entry {
+            path: PathBuf::from(name),
+            icon: None,
+            modified: None,
+            link: None,
+            target: None,
+        }
+    }
+
+    fn names(state: &[io::Result<Entry>]) -> Vec<String> {
+        state
+            .iter()
+            .map(|entry| match entry {
+                Ok(entry) => entry.path.to_string_lossy().into_owned(),
+                Err(_) => "<error>".to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn entries_sort_ignoring_case_with_errors_last() {
+        let mut state = vec![
+            Err(io::Error::other("unreadable")),
+            Ok(entry("banana.lnk")),
+            Ok(entry("Cherry.lnk")),
+            Ok(entry("apple.lnk")),
+        ];
+        state.sort_by_cached_key(|entry| entry_order(&sidecar::Sidecar::new(), entry));
+        assert_eq!(
+            names(&state),
+            ["apple.lnk", "banana.lnk", "Cherry.lnk", "<error>"]
+        );
+    }
+
+    #[test]
+    fn insert_sorted_keeps_the_order() {
+        let mut state = vec![
+            Ok(entry("apple.lnk")),
+            Ok(entry("cherry.lnk")),
+            Err(io::Error::other("unreadable")),
+        ];
+        let labels = sidecar::Sidecar::new();
+        assert_eq!(insert_sorted(&mut state, &labels, entry("Banana.lnk")), 1);
+        assert_eq!(
+            names(&state),
+            ["apple.lnk", "Banana.lnk", "cherry.lnk", "<error>"]
+        );
+    }
+
+    #[test]
+    fn labels_sort_before_file_names() {
+        let labels = sidecar::Sidecar::from([
+            ("zebra.lnk".to_string(), "Aardvark".to_string()),
+            ("b.lnk".to_string(), "Same".to_string()),
+            ("a.lnk".to_string(), "Same".to_string()),
+        ]);
+        let mut state = vec![
+            Ok(entry("b.lnk")),
+            Ok(entry("m.lnk")),
+            Ok(entry("zebra.lnk")),
+        ];
+        state.sort_by_cached_key(|entry| entry_order(&labels, entry));
+        assert_eq!(names(&state), ["zebra.lnk", "m.lnk", "b.lnk"]);
+        assert_eq!(insert_sorted(&mut state, &labels, entry("a.lnk")), 2);
+        assert_eq!(names(&state), ["zebra.lnk", "m.lnk", "a.lnk", "b.lnk"]);
+    }
+
+    #[test]
+    fn sidecar_escapes_round_trip() {
+        let folder = env::temp_dir().join(format!("kslauncher-test-{}", process::id()));
+        fs::create_dir_all(&folder).unwrap();
+        let sidecar = sidecar::Sidecar::from([
+            ("quote \"and\" \\.lnk".to_string(), r"C:\Apps".to_string()),
+            ("lines.lnk".to_string(), "one\ntwo\r\tthree".to_string()),
+            ("control.lnk".to_string(), "\u{1}\u{1f}".to_string()),
+            ("unicode.lnk".to_string(), "café 🚀".to_string()),
+            ("empty.lnk".to_string(), String::new()),
+        ]);
+        sidecar::save(&folder, sidecar::LABELS, &sidecar).unwrap();
+        let loaded = sidecar::load_checked(&folder, sidecar::LABELS);
+        let _ = fs::remove_dir_all(&folder);
+        assert_eq!(loaded, Ok(sidecar));
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn missing_file_gets_fallback_icon() {
+        let icon = get_icon(Path::new(r"C:\kslauncher-test\missing.exe"));
+        assert_eq!(icon, image::Handle::from_memory(FALLBACK_ICON));
+    }
+}
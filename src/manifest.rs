@@ -0,0 +1,59 @@
+//! Reads the application manifest embedded in an executable, which is where it asks Windows to
+//! run it as administrator.
+
+use std::{path::Path, slice};
+
+use windows::{
+    core::PCWSTR,
+    Win32::{
+        Foundation::{FreeLibrary, HANDLE, HMODULE},
+        System::LibraryLoader::{
+            FindResourceW, LoadLibraryExW, LoadResource, LockResource, SizeofResource,
+            LOAD_LIBRARY_AS_DATAFILE, LOAD_LIBRARY_AS_IMAGE_RESOURCE,
+        },
+        UI::WindowsAndMessaging::RT_MANIFEST,
+    },
+};
+
+use crate::wide::to_wide;
+
+/// The resource Windows reads an executable's own manifest from.
+const MANIFEST_ID: usize = 1;
+
+/// Executables without a manifest, or that can't be read, don't require administrator.
+pub fn requires_admin(exe: &Path) -> bool {
+    read(exe).is_some_and(|manifest| manifest.contains("requireAdministrator"))
+}
+
+fn read(exe: &Path) -> Option<String> {
+    let exe_wide = to_wide(exe.as_os_str());
+    unsafe {
+        let module = LoadLibraryExW(
+            PCWSTR(exe_wide.as_ptr()),
+            HANDLE::default(),
+            LOAD_LIBRARY_AS_DATAFILE | LOAD_LIBRARY_AS_IMAGE_RESOURCE,
+        )
+        .ok()?;
+        let manifest = read_resource(module);
+        let _ = FreeLibrary(module);
+        manifest
+    }
+}
+
+unsafe fn read_resource(module: HMODULE) -> Option<String> {
+    let resource = FindResourceW(
+        module,
+        PCWSTR(MANIFEST_ID as *const u16),
+        PCWSTR(RT_MANIFEST as usize as *const u16),
+    );
+    if resource.is_invalid() {
+        return None;
+    }
+    let data = LockResource(LoadResource(module, resource).ok()?);
+    if data.is_null() {
+        return None;
+    }
+    let len = SizeofResource(module, resource) as usize;
+    let bytes = slice::from_raw_parts(data.cast::<u8>(), len);
+    Some(String::from_utf8_lossy(bytes).into_owned())
+}
@@ -0,0 +1,146 @@
+use std::{
+    env,
+    ffi::{OsStr, OsString},
+    io, mem,
+};
+
+use windows::{
+    core::{w, PCWSTR},
+    Win32::System::Registry::{RegSetKeyValueW, HKEY_CURRENT_USER, REG_SZ},
+};
+
+use crate::wide::to_wide;
+
+const SCHEME: &str = "kslauncher://";
+
+/// Registers `kslauncher://` for the current user so that links are handed to this executable.
+pub fn register() -> io::Result<()> {
+    let mut command = OsString::from("\"");
+    command.push(env::current_exe()?);
+    command.push("\" \"%1\"");
+    let key = w!("Software\\Classes\\kslauncher");
+    set_value(key, PCWSTR::null(), "URL:kslauncher Protocol".as_ref())?;
+    set_value(key, w!("URL Protocol"), "".as_ref())?;
+    set_value(
+        w!("Software\\Classes\\kslauncher\\shell\\open\\command"),
+        PCWSTR::null(),
+        &command,
+    )?;
+    Ok(())
+}
+
+fn set_value(key: PCWSTR, name: PCWSTR, value: &OsStr) -> windows::core::Result<()> {
+    let value_wide = to_wide(value);
+    unsafe {
+        RegSetKeyValueW(
+            HKEY_CURRENT_USER,
+            key,
+            name,
+            REG_SZ.0,
+            Some(value_wide.as_ptr().cast()),
+            (value_wide.len() * mem::size_of::<u16>()) as u32,
+        )
+    }
+}
+
+/// Whether `arg` is meant as a `kslauncher://` link, valid or not.
+pub fn is_link(arg: &str) -> bool {
+    arg.get(..SCHEME.len())
+        .is_some_and(|scheme| scheme.eq_ignore_ascii_case(SCHEME))
+}
+
+/// Parses `kslauncher://open/<folder>` and `kslauncher://open/<folder>/<entry>` into the
+/// launcher folder name and optional entry name.
+pub fn parse(url: &str) -> Option<(String, Option<String>)> {
+    if !is_link(url) {
+        return None;
+    }
+    let rest = &url[SCHEME.len()..];
+    let rest = rest
+        .get(..5)
+        .filter(|action| action.eq_ignore_ascii_case("open/"))
+        .map(|_| &rest[5..])?
+        .trim_end_matches('/');
+    let (folder, entry) = match rest.split_once('/') {
+        Some((folder, entry)) => (folder, Some(entry)),
+        None => (rest, None),
+    };
+    let folder = decode(folder).filter(|name| is_plain_name(name))?;
+    let entry = match entry {
+        Some(entry) => Some(decode(entry).filter(|name| is_plain_name(name))?),
+        None => None,
+    };
+    Some((folder, entry))
+}
+
+// Links can come from anywhere, so names must not be able to step outside the launcher folder.
+fn is_plain_name(name: &str) -> bool {
+    !name.is_empty() && name != "." && name != ".." && !name.contains(['/', '\\', ':'])
+}
+
+fn decode(s: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(s.len());
+    let mut iter = s.bytes();
+    while let Some(b) = iter.next() {
+        if b == b'%' {
+            let hex = [iter.next()?, iter.next()?];
+            if !hex.iter().all(u8::is_ascii_hexdigit) {
+                return None;
+            }
+            bytes.push(u8::from_str_radix(std::str::from_utf8(&hex).ok()?, 16).ok()?);
+        } else {
+            bytes.push(b);
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parsed(folder: &str, entry: Option<&str>) -> Option<(String, Option<String>)> {
+        Some((folder.to_string(), entry.map(str::to_string)))
+    }
+
+    #[test]
+    fn parses_folders_and_entries() {
+        assert_eq!(parse("kslauncher://open/Games"), parsed("Games", None));
+        assert_eq!(
+            parse("kslauncher://open/Games/Celeste"),
+            parsed("Games", Some("Celeste"))
+        );
+        assert_eq!(parse("kslauncher://open/Games/"), parsed("Games", None));
+        assert_eq!(
+            parse("kslauncher://open/Games/Celeste/"),
+            parsed("Games", Some("Celeste"))
+        );
+        assert_eq!(parse("KSLauncher://OPEN/Games"), parsed("Games", None));
+    }
+
+    #[test]
+    fn decodes_percent_escapes() {
+        assert_eq!(
+            parse("kslauncher://open/My%20Tools/Caf%C3%A9"),
+            parsed("My Tools", Some("Café"))
+        );
+        assert_eq!(parse("kslauncher://open/Games%2"), None);
+        assert_eq!(parse("kslauncher://open/Games%+1"), None);
+        assert_eq!(parse("kslauncher://open/%FF"), None);
+    }
+
+    #[test]
+    fn rejects_names_outside_the_launcher_folder() {
+        assert_eq!(parse("kslauncher://open/.."), None);
+        assert_eq!(parse("kslauncher://open/."), None);
+        assert_eq!(parse("kslauncher://open/Games/.."), None);
+        assert_eq!(parse("kslauncher://open/%2E%2E"), None);
+        assert_eq!(parse("kslauncher://open/Games/a/b"), None);
+        assert_eq!(parse("kslauncher://open/Games%2F..%2FOther"), None);
+        assert_eq!(parse("kslauncher://open/Games/..%5C..%5Cx"), None);
+        assert_eq!(parse("kslauncher://open/C:"), None);
+        assert_eq!(parse("kslauncher://open/Games/C%3A%5Cx.exe"), None);
+        assert_eq!(parse("kslauncher://open/"), None);
+        assert_eq!(parse("kslauncher://other/Games"), None);
+    }
+}
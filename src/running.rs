@@ -0,0 +1,87 @@
+//! Finds the windows of programs that are already running, so a launch can switch to one instead
+//! of starting another copy.
+
+use std::{
+    ffi::OsString,
+    os::windows::ffi::OsStringExt,
+    path::{Path, PathBuf},
+};
+
+use windows::{
+    core::PWSTR,
+    Win32::{
+        Foundation::{CloseHandle, BOOL, HWND, LPARAM},
+        System::Threading::{
+            OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_WIN32,
+            PROCESS_QUERY_LIMITED_INFORMATION,
+        },
+        UI::WindowsAndMessaging::{
+            EnumWindows, GetWindow, GetWindowThreadProcessId, IsIconic, IsWindowVisible,
+            SetForegroundWindow, ShowWindow, GW_OWNER, SW_RESTORE,
+        },
+    },
+};
+
+/// Brings a window of the running `exe` to the front, restoring it if it's minimized. Returns
+/// `false` if no visible window belongs to it.
+pub fn focus(exe: &Path) -> bool {
+    let Some(window) = find_window(exe) else {
+        return false;
+    };
+    unsafe {
+        if IsIconic(window).as_bool() {
+            ShowWindow(window, SW_RESTORE);
+        }
+        SetForegroundWindow(window).as_bool()
+    }
+}
+
+struct Search<'a> {
+    exe: &'a Path,
+    found: Option<HWND>,
+}
+
+fn find_window(exe: &Path) -> Option<HWND> {
+    let mut search = Search { exe, found: None };
+    unsafe {
+        // Stopping early reports an error, so the result says nothing useful.
+        let _ = EnumWindows(
+            Some(visit_window),
+            LPARAM(&mut search as *mut Search as isize),
+        );
+    }
+    search.found
+}
+
+/// Only visible, unowned windows count, so tool windows and dialogs are skipped.
+unsafe extern "system" fn visit_window(window: HWND, search: LPARAM) -> BOOL {
+    let search = &mut *(search.0 as *mut Search);
+    let matches = IsWindowVisible(window).as_bool()
+        && GetWindow(window, GW_OWNER).0 == 0
+        && process_path(window).is_some_and(|path| same_path(&path, search.exe));
+    if matches {
+        search.found = Some(window);
+    }
+    (!matches).into()
+}
+
+unsafe fn process_path(window: HWND) -> Option<PathBuf> {
+    let mut process_id = 0;
+    GetWindowThreadProcessId(window, Some(&mut process_id));
+    let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, process_id).ok()?;
+    let mut path = [0; 1024];
+    let mut len = path.len() as u32;
+    let result = QueryFullProcessImageNameW(
+        process,
+        PROCESS_NAME_WIN32,
+        PWSTR(path.as_mut_ptr()),
+        &mut len,
+    );
+    let _ = CloseHandle(process);
+    result.ok()?;
+    Some(PathBuf::from(OsString::from_wide(&path[..len as usize])))
+}
+
+fn same_path(a: &Path, b: &Path) -> bool {
+    a.to_string_lossy().to_lowercase() == b.to_string_lossy().to_lowercase()
+}
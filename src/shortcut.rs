@@ -0,0 +1,202 @@
+use std::{
+    cell::Cell,
+    env,
+    ffi::{OsStr, OsString},
+    fs,
+    os::windows::ffi::OsStringExt,
+    path::{Path, PathBuf},
+    ptr,
+};
+
+use windows::{
+    core::{ComInterface, PCWSTR},
+    Win32::{
+        Foundation::MAX_PATH,
+        System::Com::{
+            CoCreateInstance, CoInitializeEx, IPersistFile, CLSCTX_INPROC_SERVER,
+            COINIT_APARTMENTTHREADED, COINIT_DISABLE_OLE1DDE, STGM_READ, STGM_READWRITE,
+        },
+        UI::{
+            Controls::INFOTIPSIZE,
+            Shell::{IShellLinkW, ShellLink, SLGP_UNCPRIORITY},
+        },
+    },
+};
+
+use crate::wide::to_wide;
+
+pub struct Shortcut {
+    pub target: PathBuf,
+    pub arguments: OsString,
+    pub working_directory: Option<PathBuf>,
+}
+
+/// A shortcut whose target is gone, along with a file of the same name that could replace it.
+#[derive(Debug, Clone)]
+pub struct Broken {
+    pub link: PathBuf,
+    pub target: PathBuf,
+    pub replacement: Option<PathBuf>,
+}
+
+pub fn is_shortcut(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("lnk"))
+}
+
+/// Initializes COM the first time a thread needs it. It stays initialized for the life of the
+/// thread, so repeated calls don't pile up initializations that are never undone.
+pub fn init_com() -> windows::core::Result<()> {
+    thread_local! {
+        static COM_INITIALIZED: Cell<bool> = const { Cell::new(false) };
+    }
+    if !COM_INITIALIZED.get() {
+        unsafe { CoInitializeEx(None, COINIT_APARTMENTTHREADED | COINIT_DISABLE_OLE1DDE)? };
+        COM_INITIALIZED.set(true);
+    }
+    Ok(())
+}
+
+pub fn resolve(path: &Path) -> windows::core::Result<Shortcut> {
+    init_com()?;
+    unsafe {
+        let link: IShellLinkW = CoCreateInstance(&ShellLink, None, CLSCTX_INPROC_SERVER)?;
+        let path_wide = to_wide(path.as_os_str());
+        link.cast::<IPersistFile>()?
+            .Load(PCWSTR(path_wide.as_ptr()), STGM_READ)?;
+
+        let mut target = [0; MAX_PATH as usize];
+        link.GetPath(&mut target, ptr::null_mut(), SLGP_UNCPRIORITY.0 as u32)?;
+        let mut arguments = [0; INFOTIPSIZE as usize];
+        link.GetArguments(&mut arguments)?;
+        let mut working_directory = [0; MAX_PATH as usize];
+        link.GetWorkingDirectory(&mut working_directory)?;
+
+        let working_directory = from_wide(&working_directory);
+        Ok(Shortcut {
+            target: PathBuf::from(from_wide(&target)),
+            arguments: from_wide(&arguments),
+            working_directory: (!working_directory.is_empty())
+                .then(|| PathBuf::from(working_directory)),
+        })
+    }
+}
+
+pub fn create(target: &Path, link: &Path) -> windows::core::Result<()> {
+    init_com()?;
+    unsafe {
+        let shell_link: IShellLinkW = CoCreateInstance(&ShellLink, None, CLSCTX_INPROC_SERVER)?;
+        let target_wide = to_wide(target.as_os_str());
+        shell_link.SetPath(PCWSTR(target_wide.as_ptr()))?;
+        if let Some(dir) = target.parent() {
+            let dir_wide = to_wide(dir.as_os_str());
+            shell_link.SetWorkingDirectory(PCWSTR(dir_wide.as_ptr()))?;
+        }
+        let link_wide = to_wide(link.as_os_str());
+        shell_link
+            .cast::<IPersistFile>()?
+            .Save(PCWSTR(link_wide.as_ptr()), true)
+    }
+}
+
+/// Points the shortcut at `target`. A working directory that was the old target's folder moves
+/// along with it, any other one is left alone.
+pub fn set_target(link: &Path, target: &Path) -> windows::core::Result<()> {
+    let old = resolve(link)?;
+    unsafe {
+        let shell_link: IShellLinkW = CoCreateInstance(&ShellLink, None, CLSCTX_INPROC_SERVER)?;
+        let link_wide = to_wide(link.as_os_str());
+        let file = shell_link.cast::<IPersistFile>()?;
+        file.Load(PCWSTR(link_wide.as_ptr()), STGM_READWRITE)?;
+        let target_wide = to_wide(target.as_os_str());
+        shell_link.SetPath(PCWSTR(target_wide.as_ptr()))?;
+        if let Some(dir) = target
+            .parent()
+            .filter(|_| old.working_directory.as_deref() == old.target.parent())
+        {
+            let dir_wide = to_wide(dir.as_os_str());
+            shell_link.SetWorkingDirectory(PCWSTR(dir_wide.as_ptr()))?;
+        }
+        file.Save(PCWSTR(link_wide.as_ptr()), true)
+    }
+}
+
+/// Checks each shortcut in `paths` and returns the ones whose target no longer exists.
+/// Shortcuts to things that aren't files, like Store apps, have no target path and are skipped.
+pub fn find_broken(paths: Vec<PathBuf>) -> Vec<Broken> {
+    paths
+        .into_iter()
+        .filter(|path| is_shortcut(path))
+        .filter_map(|link| {
+            let target = resolve(&link).ok()?.target;
+            if target.as_os_str().is_empty() || target.exists() {
+                return None;
+            }
+            let replacement = find_replacement(&target);
+            Some(Broken {
+                link,
+                target,
+                replacement,
+            })
+        })
+        .collect()
+}
+
+/// Looks for a file named like `missing`, first near where it used to be, since updates often
+/// just move an app into a new versioned folder, then in the usual install folders.
+fn find_replacement(missing: &Path) -> Option<PathBuf> {
+    const MAX_DEPTH: usize = 4;
+
+    let file_name = missing.file_name()?;
+    // A drive root is too broad to search, the install folders below cover what's worth it.
+    let nearest = missing
+        .ancestors()
+        .skip(1)
+        .take_while(|dir| dir.parent().is_some())
+        .find(|dir| dir.is_dir());
+    let install_dirs = ["ProgramFiles", "ProgramFiles(x86)", "ProgramW6432"]
+        .into_iter()
+        .filter_map(env::var_os)
+        .map(PathBuf::from)
+        .chain(env::var_os("LOCALAPPDATA").map(|dir| Path::new(&dir).join("Programs")));
+    nearest
+        .map(Path::to_path_buf)
+        .into_iter()
+        .chain(install_dirs)
+        .find_map(|dir| find_file(&dir, file_name, MAX_DEPTH))
+}
+
+fn find_file(dir: &Path, file_name: &OsStr, depth: usize) -> Option<PathBuf> {
+    let mut subdirs = vec![];
+    for entry in fs::read_dir(dir).ok()?.flatten() {
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if file_type.is_dir() {
+            subdirs.push(entry.path());
+        } else if file_type.is_file()
+            && entry
+                .file_name()
+                .to_string_lossy()
+                .eq_ignore_ascii_case(&file_name.to_string_lossy())
+        {
+            return Some(entry.path());
+        }
+    }
+    if depth == 0 {
+        return None;
+    }
+    subdirs
+        .into_iter()
+        .find_map(|subdir| find_file(&subdir, file_name, depth - 1))
+}
+
+pub fn is_executable(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("exe"))
+}
+
+fn from_wide(buf: &[u16]) -> OsString {
+    let len = buf.iter().position(|&c| c == 0).unwrap_or(buf.len());
+    OsString::from_wide(&buf[..len])
+}
@@ -0,0 +1,180 @@
+//! Metadata files stored in a launcher folder alongside its entries. Each one is a flat JSON
+//! object of strings, usually keyed by an entry's file name.
+
+use std::{collections::BTreeMap, fs, io, iter::Peekable, path::Path, str::Chars};
+
+pub const HOTKEYS: &str = "hotkeys.json";
+/// Comma separated launch options, such as `keep-open`.
+pub const OPTIONS: &str = "options.json";
+/// Subfolders that dropped files are filed into, keyed by file extension.
+pub const SORT_RULES: &str = "sort_rules.json";
+/// When each entry was last launched, in seconds since the Unix epoch.
+pub const LAUNCHED: &str = "launched.json";
+/// Names to show instead of an entry's file name.
+pub const LABELS: &str = "labels.json";
+/// `#rrggbb` colors to outline entries with.
+pub const ACCENTS: &str = "accents.json";
+/// Folders to start entries in, in place of wherever the shell would start them.
+pub const WORKING_DIRS: &str = "working_dirs.json";
+/// How the launcher window was left, keyed by setting rather than by entry.
+pub const WINDOW: &str = "window.json";
+
+/// The sidecars keyed by an entry's file name.
+pub const ENTRY_KEYED: &[&str] = &[HOTKEYS, OPTIONS, LAUNCHED, LABELS, ACCENTS, WORKING_DIRS];
+
+const ALL: &[&str] = &[
+    HOTKEYS,
+    OPTIONS,
+    SORT_RULES,
+    LAUNCHED,
+    LABELS,
+    ACCENTS,
+    WORKING_DIRS,
+    WINDOW,
+];
+
+pub type Sidecar = BTreeMap<String, String>;
+
+pub fn is_sidecar(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| ALL.contains(&name))
+}
+
+/// Missing or malformed files are treated as empty.
+pub fn load(folder: &Path, name: &str) -> Sidecar {
+    load_checked(folder, name).unwrap_or_default()
+}
+
+/// Like [`load`], but a file that exists and can't be used is an error rather than empty.
+pub fn load_checked(folder: &Path, name: &str) -> Result<Sidecar, String> {
+    match fs::read_to_string(folder.join(name)) {
+        Ok(contents) => {
+            parse(&contents).ok_or_else(|| "not a flat JSON object of strings".to_string())
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Sidecar::new()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+pub fn save(folder: &Path, name: &str, sidecar: &Sidecar) -> io::Result<()> {
+    let mut out = String::from("{\n");
+    for (i, (key, value)) in sidecar.iter().enumerate() {
+        if i != 0 {
+            out.push_str(",\n");
+        }
+        out.push_str("  ");
+        write_string(&mut out, key);
+        out.push_str(": ");
+        write_string(&mut out, value);
+    }
+    out.push_str("\n}\n");
+    fs::write(folder.join(name), out)
+}
+
+pub fn get<'a>(sidecar: &'a Sidecar, path: &Path) -> Option<&'a str> {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .and_then(|name| sidecar.get(name))
+        .map(String::as_str)
+}
+
+pub fn has_option(options: &Sidecar, path: &Path, option: &str) -> bool {
+    get(options, path).is_some_and(|options| options.split(',').any(|o| o.trim() == option))
+}
+
+fn parse(contents: &str) -> Option<Sidecar> {
+    let mut chars = contents.chars().peekable();
+    let mut sidecar = Sidecar::new();
+    expect(&mut chars, '{')?;
+    skip_whitespace(&mut chars);
+    if chars.peek() == Some(&'}') {
+        chars.next();
+    } else {
+        loop {
+            skip_whitespace(&mut chars);
+            let key = parse_string(&mut chars)?;
+            expect(&mut chars, ':')?;
+            skip_whitespace(&mut chars);
+            let value = parse_string(&mut chars)?;
+            sidecar.insert(key, value);
+            skip_whitespace(&mut chars);
+            match chars.next()? {
+                ',' => continue,
+                '}' => break,
+                _ => return None,
+            }
+        }
+    }
+    skip_whitespace(&mut chars);
+    chars.next().is_none().then_some(sidecar)
+}
+
+fn skip_whitespace(chars: &mut Peekable<Chars>) {
+    while chars.next_if(|c| c.is_whitespace()).is_some() {}
+}
+
+fn expect(chars: &mut Peekable<Chars>, expected: char) -> Option<()> {
+    skip_whitespace(chars);
+    (chars.next()? == expected).then_some(())
+}
+
+fn parse_string(chars: &mut Peekable<Chars>) -> Option<String> {
+    if chars.next()? != '"' {
+        return None;
+    }
+    let mut s = String::new();
+    loop {
+        match chars.next()? {
+            '"' => return Some(s),
+            '\\' => match chars.next()? {
+                '"' => s.push('"'),
+                '\\' => s.push('\\'),
+                '/' => s.push('/'),
+                'b' => s.push('\u{8}'),
+                'f' => s.push('\u{c}'),
+                'n' => s.push('\n'),
+                'r' => s.push('\r'),
+                't' => s.push('\t'),
+                'u' => {
+                    let high = parse_hex(chars)?;
+                    let c = if (0xD800..0xDC00).contains(&high) {
+                        if chars.next()? != '\\' || chars.next()? != 'u' {
+                            return None;
+                        }
+                        let low = parse_hex(chars)?;
+                        if !(0xDC00..0xE000).contains(&low) {
+                            return None;
+                        }
+                        char::from_u32(0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00))
+                    } else {
+                        char::from_u32(high)
+                    };
+                    s.push(c?);
+                }
+                _ => return None,
+            },
+            c => s.push(c),
+        }
+    }
+}
+
+fn parse_hex(chars: &mut Peekable<Chars>) -> Option<u32> {
+    (0..4).try_fold(0, |n, _| Some(n * 16 + chars.next()?.to_digit(16)?))
+}
+
+fn write_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
@@ -0,0 +1,20 @@
+//! Null terminated UTF-16 strings, as Windows APIs take them.
+
+use std::{ffi::OsStr, io, os::windows::ffi::OsStrExt, path::Path};
+
+pub fn to_wide(s: &OsStr) -> Vec<u16> {
+    s.encode_wide().chain(Some(0)).collect()
+}
+
+/// A NUL inside the path would cut it short, so those are rejected. Anything else, lone
+/// surrogates included, is passed through as is.
+pub fn path_to_wide(path: &Path) -> io::Result<Vec<u16>> {
+    let wide = to_wide(path.as_os_str());
+    if wide[..wide.len() - 1].contains(&0) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{} contains a NUL character", path.display()),
+        ));
+    }
+    Ok(wide)
+}